@@ -7,23 +7,43 @@ use axum::{
     Router, Server, TypedHeader,
 };
 use serde::Deserialize;
-use silkenweb::prelude::{html::div, ParentElement};
-use silkenweb_htmx_axum::{HtmxPostRequest, HtmxResponse};
+use silkenweb::{
+    dom::Dry,
+    elements::html::{br, button, div, form, label, Form},
+    prelude::{Element, HtmlElement, ParentElement},
+};
+use silkenweb_htmx_axum::{HtmxForm, HtmxPostRequest, HtmxResponse};
 use tracing::info;
 
 async fn index() -> impl IntoResponse {
     (
         TypedHeader(ContentType::html()),
-        include_str!("../index.html"),
+        include_str!("../index.html")
+            .replace("<!-- name_form -->", &name_form().freeze().to_string()),
     )
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, HtmxForm)]
 struct Name {
     first: String,
     last: String,
 }
 
+fn name_form() -> Form<Dry> {
+    let NameForm { first, last } = Name::form::<Dry>();
+
+    form()
+        .attribute("hx-post", "/form-submit")
+        .attribute("hx-target", "#form-response")
+        .child(label().r#for("first").text("First name:"))
+        .child(first.id("first"))
+        .child(br())
+        .child(br())
+        .child(label().r#for("last").text("Last name:"))
+        .child(last.id("last"))
+        .child(button().r#type("submit").text("Submit"))
+}
+
 async fn form_submit(
     HtmxPostRequest(Name { first, last }): HtmxPostRequest<Name>,
 ) -> impl IntoResponse {
@@ -1,22 +1,27 @@
 use std::{
-    collections::HashSet,
-    env, fs,
+    cell::RefCell,
+    collections::{BTreeSet, HashSet},
+    env,
+    ffi::OsStr,
+    fmt, fs, io, iter,
     path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
 
-use cssparser::{Parser, ParserInput, Token};
+use cssparser::{ParseError, Parser, ParserInput, Token};
 use itertools::Itertools;
 use lightningcss::{
-    css_modules::{self, CssModuleExports},
+    css_modules::{self, CssModuleExports, CssModuleReference},
     stylesheet::{MinifyOptions, ParserOptions, PrinterOptions, StyleSheet},
     targets::Browsers,
 };
+use parcel_sourcemap::SourceMap;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Source {
     content: String,
     dependency: Option<String>,
+    imports: Vec<String>,
 }
 
 impl Source {
@@ -24,6 +29,7 @@ impl Source {
         Self {
             content: content.into(),
             dependency: None,
+            imports: Vec::new(),
         }
     }
 
@@ -33,16 +39,68 @@ impl Source {
         let root_dir = env::var(CARGO_MANIFEST_DIR).map_err(|e| {
             format!("Error reading environment variable '{CARGO_MANIFEST_DIR}': {e}")
         })?;
-        let path = PathBuf::from(root_dir)
-            .join(path)
-            .into_os_string()
-            .into_string()
-            .expect("Expected path to be convertible to string");
+        Self::from_full_path(PathBuf::from(root_dir).join(path))
+    }
+
+    /// Load a stylesheet referenced by `specifier` in this stylesheet.
+    ///
+    /// `specifier` is relative to this stylesheet's file, or
+    /// `CARGO_MANIFEST_DIR` if this stylesheet wasn't loaded from a file.
+    pub fn referenced(&self, specifier: &str) -> Result<Self, String> {
+        match &self.dependency {
+            Some(parent) => Self::from_full_path(
+                Path::new(parent)
+                    .parent()
+                    .expect("Expected file to have a parent directory")
+                    .join(specifier),
+            ),
+            None => Self::from_path(specifier),
+        }
+    }
+
+    fn from_full_path(path: PathBuf) -> Result<Self, String> {
+        let is_sass = matches!(
+            path.extension().and_then(OsStr::to_str),
+            Some("scss" | "sass")
+        );
+        let path = path_string(path);
+
+        if is_sass {
+            return Self::from_sass(path);
+        }
 
         Ok(Self {
             content: fs::read_to_string(&path)
                 .map_err(|e| format!("Failed to read '{path}': {e}"))?,
             dependency: Some(path),
+            imports: Vec::new(),
+        })
+    }
+
+    /// Compile a SCSS or Sass file with [grass], keeping track of any imported
+    /// files.
+    ///
+    /// [grass]: https://github.com/connorskees/grass
+    fn from_sass(path: String) -> Result<Self, String> {
+        let fs = TrackingFs::default();
+        let options = grass::Options::default()
+            .fs(&fs)
+            .unicode_error_messages(false);
+        let content = grass::from_path(&path, &options)
+            .map_err(|e| format!("Failed to compile '{path}':\n{e}"))?;
+        let imports = fs
+            .read
+            .into_inner()
+            .into_iter()
+            .map(path_string)
+            .filter(|import| import != &path)
+            .unique()
+            .collect();
+
+        Ok(Self {
+            content,
+            dependency: Some(path),
+            imports,
         })
     }
 
@@ -50,7 +108,7 @@ impl Source {
         &mut self,
         validate: bool,
         transpile: Option<Transpile>,
-    ) -> Result<Option<CssModuleExports>, String> {
+    ) -> Result<Option<CssModuleExports>, Vec<TranspileError>> {
         let write_content = transpile.is_some();
 
         if validate || write_content {
@@ -58,6 +116,11 @@ impl Source {
             let pretty = transpile.as_ref().map_or(false, |t| t.pretty);
             let modules = transpile.as_ref().map_or(false, |t| t.modules);
             let nesting = transpile.as_ref().map_or(false, |t| t.nesting);
+            let dashed_idents = transpile.as_ref().map_or(false, |t| t.dashed_idents);
+            let source_map = transpile.as_ref().map_or(false, |t| t.source_map);
+            let unused_classes = transpile
+                .as_ref()
+                .map_or_else(HashSet::new, |t| t.unused_classes.clone());
             let targets = transpile.and_then(|t| t.browsers);
 
             let content = self.content.clone();
@@ -66,9 +129,17 @@ impl Source {
                 .dependency
                 .as_ref()
                 .map_or_else(|| "<content>".to_string(), String::clone);
+            let mut source_map = source_map.then(|| {
+                let mut source_map = SourceMap::new("/");
+                let source_index = source_map.add_source(manifest_relative(&filename));
+                source_map
+                    .set_source_content(source_index as usize, &content)
+                    .expect("Expected source to exist");
+                source_map
+            });
             let css_modules = modules.then(|| css_modules::Config {
                 pattern: css_modules::Pattern::default(),
-                dashed_idents: false,
+                dashed_idents,
             });
             let mut stylesheet: StyleSheet = StyleSheet::parse(
                 &content,
@@ -82,27 +153,25 @@ impl Source {
                     warnings: warnings.as_ref().map(Arc::clone),
                 },
             )
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| vec![e.into()])?;
 
             if let Some(warnings) = warnings {
                 let warnings = warnings.read().unwrap();
 
                 if !warnings.is_empty() {
-                    let warnings_text = warnings.iter().map(|w| w.to_string()).join("\n");
-
-                    return Err(warnings_text);
+                    return Err(warnings.iter().cloned().map(TranspileError::from).collect());
                 }
             }
 
             if write_content {
-                if minify {
+                if minify || !unused_classes.is_empty() {
                     // This does the structural minification and add/removes vendor prefixes.
                     stylesheet
                         .minify(MinifyOptions {
                             targets,
-                            unused_symbols: HashSet::new(),
+                            unused_symbols: unused_classes,
                         })
-                        .map_err(|e| e.to_string())?;
+                        .map_err(|e| vec![e.into()])?;
                 }
 
                 let css = stylesheet
@@ -110,15 +179,23 @@ impl Source {
                         // `minify` just controls the output format without doing more structural
                         // minification.
                         minify: !pretty && minify,
-                        source_map: None,
+                        source_map: source_map.as_mut(),
                         project_root: None,
                         targets,
                         analyze_dependencies: None,
                         pseudo_classes: None,
                     })
-                    .map_err(|e| e.to_string())?;
+                    .map_err(|e| vec![e.into()])?;
                 self.content = css.code;
 
+                if let Some(source_map) = &mut source_map {
+                    let url = source_map
+                        .to_data_url(None)
+                        .map_err(|e| vec![TranspileError::new(e.to_string())])?;
+                    self.content
+                        .push_str(&format!("\n/*# sourceMappingURL={url} */\n"));
+                }
+
                 return Ok(css.exports);
             }
         }
@@ -126,23 +203,319 @@ impl Source {
         Ok(None)
     }
 
+    /// Scope this stylesheet to elements with an attribute.
+    ///
+    /// Selectors are rewritten so the last compound selector only matches
+    /// elements with the attribute, which is named with a hash of the
+    /// stylesheet. Rules in conditional group rules, like `@media`, are scoped,
+    /// but other at-rules, like `@keyframes`, are left alone. Selectors for the
+    /// root element, like `:root` or `html`, aren't scoped, as the root element
+    /// is never in a scope.
+    ///
+    /// Returns the name of the attribute.
+    pub fn scope(&mut self) -> String {
+        let attribute = format!("data-silkenweb-{:x}", self.hash());
+        let selector = format!("[{attribute}]");
+        let mut insertions = Vec::new();
+
+        {
+            let mut parser_input = ParserInput::new(&self.content);
+            let mut input = Parser::new(&mut parser_input);
+            scope_rules(&mut input, &mut insertions);
+        }
+
+        for offset in insertions.into_iter().rev() {
+            self.content.insert_str(offset, &selector);
+        }
+
+        attribute
+    }
+
+    /// Write this stylesheet to a file in `dir`, returning the file's path.
+    ///
+    /// The file is named with the package name, the stylesheet's path relative
+    /// to `CARGO_MANIFEST_DIR` (if it was loaded from a file) and a hash of the
+    /// stylesheet. Bundling the files in name order gives the same order for
+    /// every build. Stylesheets that are no longer used aren't removed.
+    pub fn extract(&self, dir: impl AsRef<Path>) -> Result<PathBuf, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create '{}': {e}", dir.display()))?;
+        let package = env::var("CARGO_PKG_NAME").unwrap_or_default();
+        let name = match &self.dependency {
+            Some(path) => format!("{package}-{}", manifest_relative(path)),
+            None => package,
+        };
+        let name = name.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "_");
+        let path = dir.join(format!("{name}-{:08x}.css", self.hash()));
+
+        fs::write(&path, &self.content)
+            .map_err(|e| format!("Failed to write '{}': {e}", path.display()))?;
+
+        Ok(path)
+    }
+
+    /// A 32 bit FNV-1a hash of the stylesheet.
+    ///
+    /// Unlike `DefaultHasher`, this is the same for every build, so scope
+    /// attributes and extracted file names are stable. The path is relative to
+    /// `CARGO_MANIFEST_DIR`, so it doesn't depend on where the crate is.
+    fn hash(&self) -> u32 {
+        let path = self.dependency.as_deref().map(manifest_relative);
+        // `0xff` isn't valid UTF-8, so it separates the content and path.
+        let bytes = self
+            .content
+            .bytes()
+            .chain(iter::once(0xff))
+            .chain(path.into_iter().flat_map(str::bytes));
+
+        bytes.fold(0x811c_9dc5, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+        })
+    }
+
     pub fn dependency(&self) -> &Option<String> {
         &self.dependency
     }
 
+    /// Files imported by a SCSS or Sass stylesheet.
+    pub fn imports(&self) -> &[String] {
+        &self.imports
+    }
+
     pub fn content(&self) -> &str {
         &self.content
     }
 }
 
+/// A [`grass::Fs`] that records the files it reads.
+#[derive(Debug, Default)]
+struct TrackingFs {
+    read: RefCell<Vec<PathBuf>>,
+}
+
+impl grass::Fs for TrackingFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        grass::StdFs.is_dir(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        grass::StdFs.is_file(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let content = grass::StdFs.read(path)?;
+        self.read.borrow_mut().push(path.to_path_buf());
+        Ok(content)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        grass::StdFs.canonicalize(path)
+    }
+}
+
+/// `path` relative to `CARGO_MANIFEST_DIR`, if it's in that directory.
+fn manifest_relative(path: &str) -> &str {
+    env::var("CARGO_MANIFEST_DIR")
+        .ok()
+        .and_then(|root_dir| {
+            let relative = Path::new(path).strip_prefix(root_dir).ok()?;
+            let relative = relative.to_str()?;
+            // `relative` is a suffix of `path`.
+            Some(&path[path.len() - relative.len()..])
+        })
+        .unwrap_or(path)
+}
+
+fn path_string(path: PathBuf) -> String {
+    path.into_os_string()
+        .into_string()
+        .expect("Expected path to be convertible to string")
+}
+
+/// An error from parsing or transpiling a stylesheet.
+#[derive(Clone, Debug)]
+pub struct TranspileError {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl TranspileError {
+    fn new(message: String) -> Self {
+        Self {
+            message,
+            location: None,
+        }
+    }
+}
+
+impl fmt::Display for TranspileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+
+        if let Some(location) = &self.location {
+            write!(
+                f,
+                " at {}:{}:{}",
+                location.filename, location.line, location.column
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> From<lightningcss::error::Error<T>> for TranspileError {
+    fn from(error: lightningcss::error::Error<T>) -> Self {
+        Self {
+            message: error.kind.to_string(),
+            location: error.loc.map(|loc| Location {
+                filename: loc.filename,
+                line: loc.line + 1,
+                column: loc.column,
+            }),
+        }
+    }
+}
+
+/// The location of a [`TranspileError`] in the stylesheet.
+#[derive(Clone, Debug)]
+pub struct Location {
+    pub filename: String,
+    /// The line, starting at 1.
+    pub line: u32,
+    /// The column, starting at 1.
+    pub column: u32,
+}
+
+#[derive(Clone)]
 pub struct Transpile {
     pub minify: bool,
     pub pretty: bool,
     pub modules: bool,
     pub nesting: bool,
+    pub dashed_idents: bool,
+    /// Classes to remove from the stylesheet. If any are specified, the
+    /// stylesheet is minified to remove their rules. The output is only
+    /// compacted if `minify` is set and `pretty` isn't.
+    pub unused_classes: HashSet<String>,
+    /// Append an inline source map to the stylesheet.
+    pub source_map: bool,
     pub browsers: Option<Browsers>,
 }
 
+/// Resolve CSS Modules `composes` into class lists.
+///
+/// Stylesheets referenced with `composes: ... from "..."` are loaded and
+/// transpiled with the same options as the composing stylesheet.
+pub struct Composes {
+    validate: bool,
+    transpile: Transpile,
+    dependencies: Vec<(Source, CssModuleExports)>,
+}
+
+impl Composes {
+    pub fn new(validate: bool, transpile: Transpile) -> Self {
+        Self {
+            validate,
+            // Unused classes are specific to the composing stylesheet.
+            transpile: Transpile {
+                unused_classes: HashSet::new(),
+                ..transpile
+            },
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// The space separated list of classes for the export `name`.
+    ///
+    /// This includes the class itself, and all the classes it composes.
+    pub fn class_list(
+        &mut self,
+        source: &Source,
+        exports: &CssModuleExports,
+        name: &str,
+    ) -> Result<String, String> {
+        let mut classes = Vec::new();
+        self.add_classes(source, exports, name, &mut Vec::new(), &mut classes)?;
+
+        Ok(classes.into_iter().unique().join(" "))
+    }
+
+    /// The transpiled stylesheets referenced by `composes`.
+    pub fn dependencies(&self) -> impl Iterator<Item = &Source> {
+        self.dependencies.iter().map(|(source, _exports)| source)
+    }
+
+    fn add_classes(
+        &mut self,
+        source: &Source,
+        exports: &CssModuleExports,
+        name: &str,
+        visiting: &mut Vec<(Option<String>, String)>,
+        classes: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let key = (source.dependency.clone(), name.to_string());
+
+        if visiting.contains(&key) {
+            return Err(format!("`composes` for '{name}' is cyclic"));
+        }
+
+        let export = exports
+            .get(name)
+            .ok_or_else(|| format!("Class '{name}' not found for `composes`"))?;
+        visiting.push(key);
+        classes.push(export.name.clone());
+
+        for reference in &export.composes {
+            match reference {
+                CssModuleReference::Local { name } => {
+                    let local_name = exports
+                        .iter()
+                        .find_map(|(local_name, export)| {
+                            (&export.name == name).then_some(local_name)
+                        })
+                        .ok_or_else(|| format!("Class '{name}' not found for `composes`"))?;
+                    self.add_classes(source, exports, local_name, visiting, classes)?;
+                }
+                CssModuleReference::Global { name } => classes.push(name.clone()),
+                CssModuleReference::Dependency { name, specifier } => {
+                    let (dependency, dependency_exports) = self.load(source, specifier)?;
+                    self.add_classes(&dependency, &dependency_exports, name, visiting, classes)?;
+                }
+            }
+        }
+
+        visiting.pop();
+
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        parent: &Source,
+        specifier: &str,
+    ) -> Result<(Source, CssModuleExports), String> {
+        let mut source = parent.referenced(specifier)?;
+
+        if let Some(loaded) = self
+            .dependencies
+            .iter()
+            .find(|(loaded, _exports)| loaded.dependency == source.dependency)
+        {
+            return Ok(loaded.clone());
+        }
+
+        let exports = source
+            .transpile(self.validate, Some(self.transpile.clone()))
+            .map_err(|errors| errors.iter().join("\n"))?
+            .unwrap_or_default();
+        self.dependencies.push((source.clone(), exports.clone()));
+
+        Ok((source, exports))
+    }
+}
+
 pub fn class_names(css: &Source) -> impl Iterator<Item = String> {
     let mut parser_input = ParserInput::new(&css.content);
     let mut input = Parser::new(&mut parser_input);
@@ -161,3 +534,440 @@ pub fn class_names(css: &Source) -> impl Iterator<Item = String> {
 
     classes.into_iter()
 }
+
+/// Classes named in `composes` declarations in `css`.
+///
+/// This includes classes composed from other stylesheets.
+pub fn composed_classes(css: &Source) -> impl Iterator<Item = String> {
+    let mut classes = HashSet::new();
+    let mut prev_composes = false;
+    let mut in_composes = false;
+
+    for_each_token(css, |token| match token {
+        Token::WhiteSpace(_) | Token::Comment(_) => (),
+        Token::Colon if prev_composes => in_composes = true,
+        Token::Ident(ident) if in_composes => {
+            if !ident.eq_ignore_ascii_case("from") && !ident.eq_ignore_ascii_case("global") {
+                classes.insert(ident.to_string());
+            }
+        }
+        token => {
+            prev_composes =
+                matches!(token, Token::Ident(ident) if ident.eq_ignore_ascii_case("composes"));
+            in_composes = false;
+        }
+    });
+
+    classes.into_iter()
+}
+
+const USED_CLASSES_FILE: &str = "silkenweb-used-classes.txt";
+
+/// Record the `class::` identifiers used in the crate's `src` directory.
+///
+/// Call this from the crate's build script to use `css!`'s `tree_shake` and
+/// `warn_unused` options. Any path ending in `class::<identifier>` counts as a
+/// use, whichever stylesheet it refers to.
+///
+/// # Panics
+///
+/// If the source can't be read, or `OUT_DIR` can't be written to. This is
+/// intended for use from build scripts, where panicking is the appropriate way
+/// to fail.
+pub fn record_used_classes() {
+    let root_dir = env::var("CARGO_MANIFEST_DIR").expect("Expected `CARGO_MANIFEST_DIR`");
+    let out_dir = env::var("OUT_DIR").expect("Expected `OUT_DIR`");
+    let src_dir = Path::new(&root_dir).join("src");
+
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+    write_used_classes(&src_dir, Path::new(&out_dir));
+}
+
+fn write_used_classes(src_dir: &Path, out_dir: &Path) {
+    let mut used = BTreeSet::new();
+    scan_used_classes(src_dir, &mut used);
+
+    fs::write(out_dir.join(USED_CLASSES_FILE), used.into_iter().join("\n"))
+        .expect("Failed to write used classes");
+}
+
+/// The `class::` identifiers recorded by [`record_used_classes`].
+pub fn used_classes() -> Result<HashSet<String>, String> {
+    let out_dir = env::var("OUT_DIR").map_err(|_| {
+        "`OUT_DIR` isn't set. Call `silkenweb_base::css::record_used_classes` from the crate's \
+         build script."
+            .to_string()
+    })?;
+
+    read_used_classes(Path::new(&out_dir))
+}
+
+fn read_used_classes(out_dir: &Path) -> Result<HashSet<String>, String> {
+    let path = out_dir.join(USED_CLASSES_FILE);
+    let used = fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "Failed to read '{}': {e}. Call `silkenweb_base::css::record_used_classes` from the \
+             crate's build script.",
+            path.display()
+        )
+    })?;
+
+    Ok(used.lines().map(str::to_string).collect())
+}
+
+fn scan_used_classes(dir: &Path, used: &mut BTreeSet<String>) {
+    for entry in fs::read_dir(dir).expect("Failed to read source directory") {
+        let path = entry.expect("Failed to read directory entry").path();
+
+        if path.is_dir() {
+            scan_used_classes(&path, used);
+        } else if path.extension().map_or(false, |ext| ext == "rs") {
+            let source = fs::read_to_string(&path).expect("Failed to read source file");
+            used.extend(class_paths(&source));
+        }
+    }
+}
+
+/// The identifiers `x` in any `class::x` paths in `source`.
+fn class_paths(source: &str) -> impl Iterator<Item = String> + '_ {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    source
+        .match_indices("class::")
+        .filter_map(move |(index, path)| {
+            if source[..index].ends_with(is_ident_char) {
+                return None;
+            }
+
+            let ident = source[index + path.len()..].trim_start();
+            let end = ident.find(|c| !is_ident_char(c)).unwrap_or(ident.len());
+
+            (end > 0).then(|| ident[..end].to_string())
+        })
+}
+
+/// Custom properties that are defined or used in `css`.
+///
+/// The names include the `--` prefix.
+pub fn custom_properties(css: &Source) -> impl Iterator<Item = String> {
+    let mut custom_properties = HashSet::new();
+
+    for_each_token(css, |token| {
+        if let Token::Ident(ident) = token {
+            if ident.starts_with("--") {
+                custom_properties.insert(ident.to_string());
+            }
+        }
+    });
+
+    custom_properties.into_iter()
+}
+
+/// The names of `@keyframes` rules in `css`.
+pub fn keyframes(css: &Source) -> impl Iterator<Item = String> {
+    let mut keyframes = HashSet::new();
+    let mut in_keyframes_rule = false;
+
+    for_each_token(css, |token| match token {
+        Token::AtKeyword(at_rule) => {
+            in_keyframes_rule = at_rule.eq_ignore_ascii_case("keyframes")
+                || at_rule.to_ascii_lowercase().ends_with("-keyframes")
+        }
+        Token::WhiteSpace(_) | Token::Comment(_) => (),
+        Token::Ident(name) | Token::QuotedString(name) if in_keyframes_rule => {
+            keyframes.insert(name.to_string());
+            in_keyframes_rule = false;
+        }
+        _ => in_keyframes_rule = false,
+    });
+
+    keyframes.into_iter()
+}
+
+/// Call `f` on every token in `css`, including tokens in nested blocks.
+fn for_each_token(css: &Source, mut f: impl FnMut(&Token)) {
+    let mut parser_input = ParserInput::new(&css.content);
+    let mut input = Parser::new(&mut parser_input);
+
+    for_each_nested_token(&mut input, &mut f);
+}
+
+fn for_each_nested_token(input: &mut Parser, f: &mut impl FnMut(&Token)) {
+    while let Ok(token) = input.next_including_whitespace_and_comments() {
+        let token = token.clone();
+        f(&token);
+
+        if matches!(
+            token,
+            Token::Function(_)
+                | Token::ParenthesisBlock
+                | Token::SquareBracketBlock
+                | Token::CurlyBracketBlock
+        ) {
+            let _ = input.parse_nested_block(|input| -> Result<(), ParseError<()>> {
+                for_each_nested_token(input, f);
+                Ok(())
+            });
+        }
+    }
+}
+
+const CONDITIONAL_AT_RULES: &[&str] = &["media", "supports", "layer", "container", "document"];
+const LEGACY_PSEUDO_ELEMENTS: &[&str] = &["before", "after", "first-line", "first-letter"];
+
+/// Find the offsets to insert the scope selector for each rule in `input`.
+fn scope_rules(input: &mut Parser, insertions: &mut Vec<usize>) {
+    loop {
+        let state = input.state();
+
+        match input.next() {
+            Ok(Token::AtKeyword(name)) => {
+                let conditional = CONDITIONAL_AT_RULES
+                    .iter()
+                    .any(|rule| name.eq_ignore_ascii_case(rule));
+
+                loop {
+                    match input.next() {
+                        Ok(Token::CurlyBracketBlock) => {
+                            if conditional {
+                                let _ = input.parse_nested_block(
+                                    |input| -> Result<(), ParseError<()>> {
+                                        scope_rules(input, insertions);
+                                        Ok(())
+                                    },
+                                );
+                            }
+
+                            break;
+                        }
+                        Ok(Token::Semicolon) | Err(_) => break,
+                        Ok(_) => (),
+                    }
+                }
+            }
+            Ok(_) => {
+                input.reset(&state);
+                scope_selectors(input, insertions);
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+/// Find the insertion offsets for a selector list, consuming it and the rule's
+/// block.
+///
+/// The scope goes at the end of the last compound selector, but before any
+/// pseudo element.
+fn scope_selectors(input: &mut Parser, insertions: &mut Vec<usize>) {
+    let mut end = None;
+    let mut pseudo_element = None;
+    let mut compound_ended = false;
+    let mut matches_root = false;
+
+    loop {
+        let start = input.position().byte_index();
+        let token = match input.next_including_whitespace_and_comments() {
+            Ok(token) => token.clone(),
+            Err(_) => return,
+        };
+
+        match token {
+            Token::WhiteSpace(_) | Token::Comment(_) | Token::Delim('>' | '+' | '~') => {
+                compound_ended = true
+            }
+            Token::Comma | Token::CurlyBracketBlock => {
+                if !matches_root {
+                    insertions.extend(pseudo_element.or(end));
+                }
+
+                end = None;
+                pseudo_element = None;
+                compound_ended = false;
+                matches_root = false;
+
+                if token == Token::CurlyBracketBlock {
+                    return;
+                }
+            }
+            token => {
+                if compound_ended || end.is_none() {
+                    matches_root = starts_root_selector(&token, input);
+                }
+
+                if compound_ended {
+                    pseudo_element = None;
+                    compound_ended = false;
+                }
+
+                if matches!(
+                    token,
+                    Token::Function(_) | Token::ParenthesisBlock | Token::SquareBracketBlock
+                ) {
+                    let _ = input.parse_nested_block(|_| -> Result<(), ParseError<()>> { Ok(()) });
+                }
+
+                if token == Token::Colon && pseudo_element.is_none() {
+                    let state = input.state();
+                    let is_pseudo_element = match input.next_including_whitespace_and_comments() {
+                        Ok(Token::Colon) => true,
+                        Ok(Token::Ident(name)) => LEGACY_PSEUDO_ELEMENTS
+                            .iter()
+                            .any(|element| name.eq_ignore_ascii_case(element)),
+                        _ => false,
+                    };
+                    input.reset(&state);
+
+                    if is_pseudo_element {
+                        pseudo_element = Some(start);
+                    }
+                }
+
+                end = Some(input.position().byte_index());
+            }
+        }
+    }
+}
+
+/// Does a compound selector starting with `token` only match the root element,
+/// like `:root` or `html.dark`?
+fn starts_root_selector(token: &Token, input: &mut Parser) -> bool {
+    match token {
+        Token::Ident(name) => name.eq_ignore_ascii_case("html"),
+        Token::Colon => {
+            let state = input.state();
+            let is_root = matches!(
+                input.next_including_whitespace_and_comments(),
+                Ok(Token::Ident(name)) if name.eq_ignore_ascii_case("root")
+            );
+            input.reset(&state);
+            is_root
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+    };
+
+    use super::{
+        class_paths, composed_classes, manifest_relative, read_used_classes, used_classes,
+        write_used_classes, Source,
+    };
+
+    #[test]
+    fn extract_content() {
+        let dir = test_dir("content");
+        let source = Source::from_content(".a { color: red; }");
+        let path = source.extract(&dir).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), ".a { color: red; }");
+        assert_eq!(path, source.extract(&dir).unwrap(), "Names are stable");
+        assert!(file_name(&path).starts_with("silkenweb-base-"));
+    }
+
+    #[test]
+    fn extract_order() {
+        let dir = test_dir("order");
+        let sources = ["b.css", "a.css"].map(|name| {
+            let path = dir.join(name);
+            fs::write(&path, ".a { color: red; }").unwrap();
+            Source::from_path(path).unwrap()
+        });
+        let bundle_dir = dir.join("bundle");
+        let mut extracted: Vec<String> = sources
+            .iter()
+            .map(|source| file_name(&source.extract(&bundle_dir).unwrap()))
+            .collect();
+
+        assert_ne!(extracted[0], extracted[1]);
+        assert!(extracted[0].contains("_b_css-"));
+        extracted.sort();
+        assert!(extracted[0].contains("_a_css-"), "Sorted by path");
+    }
+
+    #[test]
+    fn relative_paths() {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+
+        assert_eq!(
+            manifest_relative(&format!("{manifest_dir}/src/style.css")),
+            "src/style.css"
+        );
+        assert_eq!(
+            manifest_relative("/elsewhere/style.css"),
+            "/elsewhere/style.css"
+        );
+    }
+
+    #[test]
+    fn record_used_classes() {
+        let dir = test_dir("used");
+        let src_dir = dir.join("src");
+        fs::create_dir_all(src_dir.join("module")).unwrap();
+        fs::write(src_dir.join("lib.rs"), "div().class(class::BUTTON)").unwrap();
+        fs::write(
+            src_dir.join("module").join("mod.rs"),
+            "css::class::active(); css::class::BUTTON",
+        )
+        .unwrap();
+        fs::write(src_dir.join("notes.txt"), "class::IGNORED").unwrap();
+
+        write_used_classes(&src_dir, &dir);
+        let mut used: Vec<String> = read_used_classes(&dir).unwrap().into_iter().collect();
+        used.sort();
+
+        assert_eq!(used, ["BUTTON", "active"]);
+    }
+
+    #[test]
+    fn used_classes_without_build_script() {
+        if env::var_os("OUT_DIR").is_none() {
+            assert!(used_classes().unwrap_err().contains("record_used_classes"));
+        }
+
+        assert!(read_used_classes(&test_dir("not-recorded"))
+            .unwrap_err()
+            .contains("record_used_classes"));
+    }
+
+    #[test]
+    fn class_paths_in_source() {
+        let source = "class::A; css::class:: b_1(); subclass::C; class::; my_class::D";
+        let paths: Vec<String> = class_paths(source).collect();
+
+        assert_eq!(paths, ["A", "b_1"]);
+    }
+
+    #[test]
+    fn composed() {
+        let source = Source::from_content(
+            r#"
+            .a { composes: b c from "other.css"; color: red; }
+            .d { composes: e from global; }
+            .f { composes: g; }
+            "#,
+        );
+        let mut composed: Vec<String> = composed_classes(&source).collect();
+        composed.sort();
+
+        assert_eq!(composed, ["b", "c", "e", "g"]);
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir()
+            .join(format!("silkenweb-base-{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file_name(path: &Path) -> String {
+        path.file_name().unwrap().to_str().unwrap().to_string()
+    }
+}
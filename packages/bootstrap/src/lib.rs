@@ -12,6 +12,7 @@ pub mod button_group;
 pub mod dropdown;
 pub mod icon;
 pub mod tab_bar;
+pub mod theme;
 pub mod utility;
 
 pub mod css {
@@ -0,0 +1,234 @@
+//! Runtime theming with CSS custom properties.
+//!
+//! [`themed`] sets bootstrap's CSS custom properties for an element and its
+//! descendants. The light or dark theme is chosen with the
+//! `prefers-color-scheme` media query, unless it's overridden. The themes are
+//! applied with a stylesheet and inline styles, so they're included in server
+//! side rendered HTML.
+//!
+//! Bootstrap 5.2 components that define their own colour variables, like
+//! buttons, aren't affected by the theme.
+//!
+//! # Example
+//!
+//! ```
+//! # use futures_signals::signal::Mutable;
+//! # use silkenweb::{
+//! #     dom::Dry,
+//! #     elements::html::{div, Div},
+//! #     task::{render_now, server},
+//! # };
+//! # use silkenweb_bootstrap::theme::{themed, Theme};
+//! # server::block_on(server::scope(async {
+//! let overridden = Mutable::new(None);
+//! let app: Div<Dry> = themed(div(), &Theme::light(), &Theme::dark(), &overridden);
+//! let app = app.freeze();
+//!
+//! // Follow the user's preference, with the stylesheet.
+//! render_now().await;
+//! assert!(!app.to_string().contains("style="));
+//!
+//! // Use the dark theme, whatever the user's preference.
+//! overridden.set(Some(Theme::dark()));
+//! render_now().await;
+//! assert!(app.to_string().contains("color-scheme: dark;"));
+//! # }))
+//! ```
+use std::{fmt, iter};
+
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    document::Document,
+    dom::DefaultDom,
+    elements::html::style,
+    node::element::{Element, ParentElement},
+    value::Sig,
+};
+
+/// An RGB colour.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// The comma separated components, as used by bootstrap's `-rgb` custom
+    /// properties.
+    pub fn components(self) -> String {
+        format!("{}, {}, {}", self.0, self.1, self.2)
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// The value of the CSS `color-scheme` property.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColourScheme {
+    Light,
+    Dark,
+}
+
+impl ColourScheme {
+    fn as_str(self) -> &'static str {
+        match self {
+            ColourScheme::Light => "light",
+            ColourScheme::Dark => "dark",
+        }
+    }
+}
+
+/// A bootstrap theme.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Theme {
+    pub colour_scheme: ColourScheme,
+    pub primary: Rgb,
+    pub secondary: Rgb,
+    pub success: Rgb,
+    pub info: Rgb,
+    pub warning: Rgb,
+    pub danger: Rgb,
+    pub light: Rgb,
+    pub dark: Rgb,
+    pub body_colour: Rgb,
+    pub body_background: Rgb,
+    pub border_colour: Rgb,
+    pub link_colour: Rgb,
+}
+
+impl Theme {
+    /// Bootstrap's default theme.
+    pub fn light() -> Self {
+        Self {
+            colour_scheme: ColourScheme::Light,
+            primary: Rgb(0x0d, 0x6e, 0xfd),
+            secondary: Rgb(0x6c, 0x75, 0x7d),
+            success: Rgb(0x19, 0x87, 0x54),
+            info: Rgb(0x0d, 0xca, 0xf0),
+            warning: Rgb(0xff, 0xc1, 0x07),
+            danger: Rgb(0xdc, 0x35, 0x45),
+            light: Rgb(0xf8, 0xf9, 0xfa),
+            dark: Rgb(0x21, 0x25, 0x29),
+            body_colour: Rgb(0x21, 0x25, 0x29),
+            body_background: Rgb(0xff, 0xff, 0xff),
+            border_colour: Rgb(0xde, 0xe2, 0xe6),
+            link_colour: Rgb(0x0d, 0x6e, 0xfd),
+        }
+    }
+
+    /// A dark theme, based on bootstrap 5.3's dark mode.
+    pub fn dark() -> Self {
+        Self {
+            colour_scheme: ColourScheme::Dark,
+            body_colour: Rgb(0xde, 0xe2, 0xe6),
+            body_background: Rgb(0x21, 0x25, 0x29),
+            border_colour: Rgb(0x49, 0x50, 0x57),
+            link_colour: Rgb(0x6e, 0xa8, 0xfe),
+            ..Self::light()
+        }
+    }
+
+    /// The CSS properties for this theme, as `(name, value)` pairs.
+    ///
+    /// The names are the same, and in the same order, for every theme.
+    pub fn properties(&self) -> Vec<(&'static str, String)> {
+        let mut properties = vec![("color-scheme", self.colour_scheme.as_str().to_string())];
+
+        for (name, rgb_name, colour) in [
+            ("--bs-primary", "--bs-primary-rgb", self.primary),
+            ("--bs-secondary", "--bs-secondary-rgb", self.secondary),
+            ("--bs-success", "--bs-success-rgb", self.success),
+            ("--bs-info", "--bs-info-rgb", self.info),
+            ("--bs-warning", "--bs-warning-rgb", self.warning),
+            ("--bs-danger", "--bs-danger-rgb", self.danger),
+            ("--bs-light", "--bs-light-rgb", self.light),
+            ("--bs-dark", "--bs-dark-rgb", self.dark),
+            ("--bs-body-color", "--bs-body-color-rgb", self.body_colour),
+            ("--bs-body-bg", "--bs-body-bg-rgb", self.body_background),
+        ] {
+            properties.push((name, colour.to_string()));
+            properties.push((rgb_name, colour.components()));
+        }
+
+        properties.push(("--bs-border-color", self.border_colour.to_string()));
+        properties.push(("--bs-link-color", self.link_colour.to_string()));
+
+        properties
+    }
+
+    fn declarations(&self) -> String {
+        self.properties()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value};"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Apply themes to `root` and its descendants.
+///
+/// `light` or `dark` is used, depending on `prefers-color-scheme`, unless
+/// `overridden` is set. `root` also gets the theme's text and background
+/// colours, so it doesn't need to be `<body>`.
+pub fn themed<E: Element>(
+    root: E,
+    light: &Theme,
+    dark: &Theme,
+    overridden: &Mutable<Option<Theme>>,
+) -> E {
+    let root = root.class(mount_stylesheet(light, dark));
+    let properties = overridden
+        .signal_ref(|theme| theme.as_ref().map(Theme::properties))
+        .broadcast();
+
+    light
+        .properties()
+        .into_iter()
+        .enumerate()
+        .fold(root, |root, (index, (name, _value))| {
+            root.style_property(
+                name,
+                Sig(properties.signal_ref(move |properties| {
+                    properties
+                        .as_ref()
+                        .map(|properties| properties[index].1.clone())
+                })),
+            )
+        })
+}
+
+/// Mount a stylesheet for `light` and `dark`, returning the class to apply
+/// it.
+fn mount_stylesheet(light: &Theme, dark: &Theme) -> String {
+    let light = light.declarations();
+    let dark = dark.declarations();
+    let class = format!("bs-theme-{:x}", hash(&light, &dark));
+    let stylesheet = format!(
+        ".{class} {{ {light} {COLOURS} }} \
+         @media (prefers-color-scheme: dark) {{ .{class} {{ {dark} }} }}"
+    );
+
+    DefaultDom::mount_in_head(
+        &format!("silkenweb-style:{class}"),
+        style().text(stylesheet),
+    );
+
+    class
+}
+
+/// Bootstrap only sets these on `<body>`, so set them on the root too.
+const COLOURS: &str = "color: var(--bs-body-color); background-color: var(--bs-body-bg);";
+
+/// A 32 bit FNV-1a hash of the declarations.
+///
+/// Unlike `DefaultHasher`, this is the same for every build, so server side
+/// rendered class names match the client's.
+fn hash(light: &str, dark: &str) -> u32 {
+    // `0xff` isn't valid UTF-8, so it separates the declarations.
+    let bytes = light.bytes().chain(iter::once(0xff)).chain(dark.bytes());
+
+    bytes.fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
@@ -0,0 +1,290 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro_error::{abort, proc_macro_error};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_macro_input, Attribute, Data, DeriveInput, Field, Fields, GenericArgument,
+    Ident, Lit, Meta, NestedMeta, PathArguments, Type,
+};
+
+#[proc_macro_derive(HtmxForm)]
+#[proc_macro_error]
+pub fn derive_htmx_form(item: TokenStream) -> TokenStream {
+    let item: DeriveInput = parse_macro_input!(item);
+
+    if !item.generics.params.is_empty() {
+        abort!(item.generics, "Generic forms are unsupported");
+    }
+
+    let rename_all = serde_attrs(&item.attrs)
+        .find_map(|meta| deserialize_name(&meta, "rename_all"))
+        .map(|(rule, span)| {
+            RenameRule::parse(&rule)
+                .unwrap_or_else(|| abort!(span, "Unknown `rename_all` rule: \"{}\"", rule))
+        });
+
+    let fields = match &item.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => abort!(item.ident, "Only structs with named fields are supported"),
+        },
+        _ => abort!(item.ident, "Only structs are supported"),
+    };
+
+    let fields: Vec<FormField> = fields
+        .iter()
+        .filter_map(|field| FormField::new(field, rename_all))
+        .collect();
+
+    let vis = &item.vis;
+    let ident = &item.ident;
+    let form_ident = format_ident!("{}Form", ident);
+    let field_idents = fields.iter().map(|field| &field.ident);
+    let field_types = fields.iter().map(FormField::inputs_type);
+    let field_inputs = fields.iter().map(FormField::inputs);
+    let doc = format!("The inputs for a [`{ident}`] form, named to match its fields.");
+    // `D` would be unused if every field is skipped.
+    let (phantom_field, phantom_init) = if fields.is_empty() {
+        (
+            quote!(phantom: ::std::marker::PhantomData<D>,),
+            quote!(phantom: ::std::marker::PhantomData,),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
+    quote!(
+        #[doc = #doc]
+        #vis struct #form_ident<D: ::silkenweb::dom::Dom = ::silkenweb::dom::DefaultDom> {
+            #(#vis #field_idents: #field_types,)*
+            #phantom_field
+        }
+
+        impl ::silkenweb_htmx_axum::HtmxForm for #ident {
+            type Inputs<D: ::silkenweb::dom::Dom> = #form_ident<D>;
+
+            fn form<D: ::silkenweb::dom::Dom>() -> Self::Inputs<D> {
+                #form_ident {
+                    #(#field_inputs,)*
+                    #phantom_init
+                }
+            }
+        }
+    )
+    .into()
+}
+
+struct FormField {
+    ident: Ident,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    Input { name: String, input_type: InputType },
+    Flatten(Type),
+}
+
+impl FormField {
+    /// The form field for `field`, or `None` if `serde` skips it.
+    fn new(field: &Field, rename_all: Option<RenameRule>) -> Option<Self> {
+        let ident = field.ident.clone().unwrap();
+        let mut name = None;
+        let mut flatten = false;
+
+        for meta in serde_attrs(&field.attrs) {
+            if meta.path().is_ident("skip") || meta.path().is_ident("skip_deserializing") {
+                return None;
+            }
+
+            if meta.path().is_ident("flatten") {
+                flatten = true;
+            }
+
+            if let Some((rename, _span)) = deserialize_name(&meta, "rename") {
+                name = Some(rename);
+            }
+        }
+
+        let kind = if flatten {
+            FieldKind::Flatten(field.ty.clone())
+        } else {
+            let name = name.unwrap_or_else(|| {
+                let name = ident.unraw().to_string();
+
+                match rename_all {
+                    Some(rule) => rule.apply(&name),
+                    None => name,
+                }
+            });
+
+            FieldKind::Input {
+                name,
+                input_type: InputType::new(&field.ty),
+            }
+        };
+
+        Some(Self { ident, kind })
+    }
+
+    fn inputs_type(&self) -> proc_macro2::TokenStream {
+        match &self.kind {
+            FieldKind::Input { .. } => quote!(::silkenweb::elements::html::Input<D>),
+            FieldKind::Flatten(ty) => {
+                quote!(<#ty as ::silkenweb_htmx_axum::HtmxForm>::Inputs<D>)
+            }
+        }
+    }
+
+    fn inputs(&self) -> proc_macro2::TokenStream {
+        let ident = &self.ident;
+
+        match &self.kind {
+            FieldKind::Input { name, input_type } => {
+                let input = quote!(::silkenweb::elements::html::input().name(#name));
+                let input = match input_type {
+                    InputType::Text => quote!(#input.r#type("text")),
+                    InputType::Number => quote!(#input.r#type("number")),
+                    InputType::Checkbox => quote!(#input.r#type("checkbox").value("true")),
+                };
+
+                quote!(#ident: #input)
+            }
+            FieldKind::Flatten(ty) => {
+                quote!(#ident: <#ty as ::silkenweb_htmx_axum::HtmxForm>::form::<D>())
+            }
+        }
+    }
+}
+
+/// The `type` of `<input>` for a field, based on the field's Rust type.
+enum InputType {
+    Text,
+    Number,
+    Checkbox,
+}
+
+impl InputType {
+    fn new(ty: &Type) -> Self {
+        let segment = match ty {
+            Type::Path(ty) => match ty.path.segments.last() {
+                Some(segment) => segment,
+                None => return Self::Text,
+            },
+            _ => return Self::Text,
+        };
+
+        match segment.ident.to_string().as_str() {
+            "bool" => Self::Checkbox,
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+            | "u128" | "usize" | "f32" | "f64" => Self::Number,
+            "Option" => match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(GenericArgument::Type(ty)) => Self::new(ty),
+                    _ => Self::Text,
+                },
+                _ => Self::Text,
+            },
+            _ => Self::Text,
+        }
+    }
+}
+
+/// A `serde` `rename_all` rule.
+#[derive(Copy, Clone)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Rename a `snake_case` field name, in the same way as `serde`.
+    fn apply(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => field
+                .split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                        .unwrap_or_default()
+                })
+                .collect(),
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// The name used for deserializing from `#[serde(attr = "name")]` or
+/// `#[serde(attr(deserialize = "name"))]`.
+fn deserialize_name(meta: &Meta, attr: &str) -> Option<(String, Span)> {
+    match meta {
+        Meta::NameValue(name_value) if name_value.path.is_ident(attr) => {
+            Some(lit_str(&name_value.lit))
+        }
+        Meta::List(list) if list.path.is_ident(attr) => {
+            list.nested.iter().find_map(|nested| match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("deserialize") =>
+                {
+                    Some(lit_str(&name_value.lit))
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> impl Iterator<Item = Meta> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .flat_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.into_iter().collect::<Vec<_>>(),
+            Ok(meta) => abort!(meta, "Expected a list of `serde` attributes"),
+            Err(e) => abort!(e.span(), "{}", e),
+        })
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(meta) => Some(meta),
+            NestedMeta::Lit(_) => None,
+        })
+}
+
+fn lit_str(lit: &Lit) -> (String, Span) {
+    match lit {
+        Lit::Str(lit) => (lit.value(), lit.span()),
+        _ => abort!(lit, "Expected a string literal"),
+    }
+}
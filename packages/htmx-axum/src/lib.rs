@@ -1,29 +1,229 @@
+use std::convert::Infallible;
+
 use async_trait::async_trait;
 use axum::{
     body::{Bytes, HttpBody},
-    extract::FromRequest,
+    extract::{FromRequest, FromRequestParts},
     headers::ContentType,
-    http::{self, Request},
+    http::{
+        self,
+        header::{HeaderMap, HeaderName, HeaderValue},
+        request::Parts,
+        Request,
+    },
     response::{IntoResponse, Response},
     BoxError, TypedHeader,
 };
 use serde::de::DeserializeOwned;
-use silkenweb::{dom::Dry, prelude::Node};
+use silkenweb::{
+    dom::{Dom, Dry},
+    node::element::{Element, GenericElement},
+    prelude::Node,
+};
+
+mod form;
 
-pub struct HtmxResponse(Node<Dry>);
+/// An HTML fragment response for htmx.
+///
+/// Response headers can be added with the builder methods, and extra elements
+/// can be swapped in out of band with [`Self::out_of_band`]. htmx only reads
+/// one value for each header, so setting a header again replaces it.
+///
+/// If any header values are invalid, for example because they contain a
+/// newline, the response is an internal server error, with a message saying
+/// which header was invalid.
+pub struct HtmxResponse {
+    node: Node<Dry>,
+    out_of_band: Vec<Node<Dry>>,
+    headers: Vec<(HeaderName, String)>,
+    triggers: Vec<String>,
+}
 
 impl HtmxResponse {
     pub fn new(node: impl Into<Node<Dry>>) -> Self {
-        Self(node.into())
+        Self {
+            node: node.into(),
+            out_of_band: Vec::new(),
+            headers: Vec::new(),
+            triggers: Vec::new(),
+        }
+    }
+
+    /// Swap `element` in out of band, using `swap`.
+    ///
+    /// This sets `hx-swap-oob` on `element`, so htmx will swap it in place of
+    /// the element with the same `id`. `element` should have an `id`
+    /// attribute. This can be called multiple times to add multiple out of
+    /// band elements.
+    pub fn out_of_band(mut self, swap: Swap, element: impl Into<GenericElement<Dry>>) -> Self {
+        self.out_of_band.push(
+            element
+                .into()
+                .attribute("hx-swap-oob", swap.as_str())
+                .into(),
+        );
+        self
+    }
+
+    /// Set the `HX-Redirect` header to do a client side redirect to `url`.
+    pub fn redirect(self, url: impl Into<String>) -> Self {
+        self.header("hx-redirect", url)
+    }
+
+    /// Set the `HX-Push-Url` header to push `url` into the browser history.
+    pub fn push_url(self, url: impl Into<String>) -> Self {
+        self.header("hx-push-url", url)
+    }
+
+    /// Set the `HX-Reswap` header to override how the response is swapped in.
+    pub fn reswap(self, swap: Swap) -> Self {
+        self.header("hx-reswap", swap.as_str())
+    }
+
+    /// Add `event` to the `HX-Trigger` header to trigger a client side event.
+    ///
+    /// This can be called multiple times to trigger multiple events. They're
+    /// sent as a single JSON object, like `{"first":null,"second":null}`.
+    pub fn trigger(mut self, event: impl Into<String>) -> Self {
+        self.triggers.push(event.into());
+        self
+    }
+
+    fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers
+            .push((HeaderName::from_static(name), value.into()));
+        self
+    }
+
+    /// Build the headers, or an error message for the first invalid header
+    /// value.
+    fn header_map(&self) -> Result<HeaderMap, String> {
+        let mut headers = HeaderMap::new();
+        let triggers = (!self.triggers.is_empty()).then(|| {
+            let events: serde_json::Map<String, serde_json::Value> = self
+                .triggers
+                .iter()
+                .map(|event| (event.clone(), serde_json::Value::Null))
+                .collect();
+
+            (
+                HeaderName::from_static("hx-trigger"),
+                serde_json::Value::Object(events).to_string(),
+            )
+        });
+
+        for (name, value) in self.headers.iter().cloned().chain(triggers) {
+            let header_value = HeaderValue::try_from(&value)
+                .map_err(|_| format!("Invalid value for `{name}` header: {value:?}"))?;
+            headers.insert(name, header_value);
+        }
+
+        Ok(headers)
     }
 }
 
 impl IntoResponse for HtmxResponse {
     fn into_response(self) -> Response {
-        (TypedHeader(ContentType::html()), self.0.to_string()).into_response()
+        let headers = match self.header_map() {
+            Ok(headers) => headers,
+            Err(message) => {
+                return (http::StatusCode::INTERNAL_SERVER_ERROR, message).into_response()
+            }
+        };
+        let mut body = self.node.to_string();
+
+        for node in &self.out_of_band {
+            body.push_str(&node.to_string());
+        }
+
+        (headers, TypedHeader(ContentType::html()), body).into_response()
+    }
+}
+
+/// How htmx should swap content into the DOM.
+///
+/// See the [htmx docs](https://htmx.org/attributes/hx-swap/).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Swap {
+    InnerHtml,
+    OuterHtml,
+    BeforeBegin,
+    AfterBegin,
+    BeforeEnd,
+    AfterEnd,
+    Delete,
+    None,
+}
+
+impl Swap {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Swap::InnerHtml => "innerHTML",
+            Swap::OuterHtml => "outerHTML",
+            Swap::BeforeBegin => "beforebegin",
+            Swap::AfterBegin => "afterbegin",
+            Swap::BeforeEnd => "beforeend",
+            Swap::AfterEnd => "afterend",
+            Swap::Delete => "delete",
+            Swap::None => "none",
+        }
     }
 }
 
+/// Generate typed form inputs for a form struct.
+///
+/// This generates a `<StructName>Form` struct, with an `<input>` for each
+/// field, and implements [`HtmxForm`]. Each `<input>` has its `name` attribute
+/// set to the field name, taking `#[serde(rename = "...")]` and
+/// `#[serde(rename_all = "...")]` into account.
+///
+/// The input `type` depends on the field type:
+///
+/// - Integer and floating point fields use `number`.
+/// - `bool` fields use `checkbox`, with a value of `true`. Unchecked boxes
+///   aren't submitted, so `bool` fields should have `#[serde(default)]`.
+/// - `Option<T>` fields use the type for `T`. [`HtmxPostRequest`] treats a
+///   blank value as `None`.
+/// - Anything else uses `text`.
+///
+/// Fields with `#[serde(skip)]` or `#[serde(skip_deserializing)]` don't have
+/// an input. Fields with `#[serde(flatten)]` must implement [`HtmxForm`], and
+/// use its inputs.
+///
+/// ```
+/// # use serde::Deserialize;
+/// # use silkenweb::{
+/// #     dom::Dry,
+/// #     elements::html::{form, Form},
+/// #     prelude::ParentElement,
+/// # };
+/// # use silkenweb_htmx_axum::HtmxForm;
+/// #[derive(Deserialize, HtmxForm)]
+/// struct Name {
+///     first: String,
+///     last: String,
+/// }
+///
+/// let NameForm { first, last } = Name::form::<Dry>();
+/// let form: Form<Dry> = form().child(first).child(last);
+/// ```
+pub use silkenweb_htmx_axum_macros::HtmxForm;
+
+/// A form that can be submitted as an [`HtmxPostRequest`].
+///
+/// Use `#[derive(HtmxForm)]` to implement this, so the form inputs and the
+/// request type can't get out of sync.
+pub trait HtmxForm: DeserializeOwned {
+    type Inputs<D: Dom>;
+
+    /// The named inputs for this form.
+    fn form<D: Dom>() -> Self::Inputs<D>;
+}
+
+/// Extract a form submitted by an htmx post request.
+///
+/// Blank values deserialize to `None` for `Option` fields, so an empty optional
+/// input isn't an error.
 pub struct HtmxPostRequest<T>(pub T);
 
 #[async_trait]
@@ -41,8 +241,74 @@ where
         let bytes = Bytes::from_request(req, state)
             .await
             .map_err(|_| http::StatusCode::BAD_REQUEST)?;
-        serde_urlencoded::from_bytes(&bytes)
+        form::from_bytes(&bytes)
             .map_err(|_| http::StatusCode::BAD_REQUEST)
             .map(HtmxPostRequest)
     }
 }
+
+macro_rules! flag_headers {
+    ($($(#[$meta:meta])* $name:ident = $header:literal),* $(,)?) => {$(
+        $(#[$meta])*
+        pub struct $name(pub bool);
+
+        #[async_trait]
+        impl<State: Send + Sync> FromRequestParts<State> for $name {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                _state: &State,
+            ) -> Result<Self, Self::Rejection> {
+                Ok(Self(header_text(parts, $header).as_deref() == Some("true")))
+            }
+        }
+    )*};
+}
+
+macro_rules! text_headers {
+    ($($(#[$meta:meta])* $name:ident = $header:literal),* $(,)?) => {$(
+        $(#[$meta])*
+        pub struct $name(pub Option<String>);
+
+        #[async_trait]
+        impl<State: Send + Sync> FromRequestParts<State> for $name {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                _state: &State,
+            ) -> Result<Self, Self::Rejection> {
+                Ok(Self(header_text(parts, $header)))
+            }
+        }
+    )*};
+}
+
+flag_headers!(
+    /// Extract the `HX-Request` header, which is `true` for all htmx requests.
+    HxRequest = "hx-request",
+    /// Extract the `HX-Boosted` header, which is `true` if the request is via
+    /// an element using `hx-boost`.
+    HxBoosted = "hx-boosted",
+);
+
+text_headers!(
+    /// Extract the `HX-Target` header, which is the `id` of the target
+    /// element, if it exists.
+    HxTarget = "hx-target",
+    /// Extract the `HX-Trigger` header, which is the `id` of the triggered
+    /// element, if it exists.
+    HxTrigger = "hx-trigger",
+    /// Extract the `HX-Current-URL` header, which is the current URL of the
+    /// browser.
+    HxCurrentUrl = "hx-current-url",
+);
+
+fn header_text(parts: &Parts, name: &str) -> Option<String> {
+    parts
+        .headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
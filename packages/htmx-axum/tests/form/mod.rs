@@ -0,0 +1,132 @@
+use axum::{body::Body, extract::FromRequest, http::Request};
+use serde::Deserialize;
+use silkenweb::{dom::Dry, elements::html::Input};
+use silkenweb_htmx_axum::{HtmxForm, HtmxPostRequest};
+
+#[allow(dead_code)]
+#[derive(Deserialize, HtmxForm)]
+#[serde(rename_all = "camelCase")]
+struct Person {
+    first_name: String,
+    #[serde(rename = "years")]
+    age: u32,
+    nickname: Option<String>,
+    #[serde(default)]
+    subscribed: bool,
+    #[serde(skip)]
+    id: usize,
+    #[serde(flatten)]
+    address: Address,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize, HtmxForm)]
+struct Address {
+    #[serde(rename(deserialize = "street_address"))]
+    street: String,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize, HtmxForm)]
+struct Skipped {
+    #[serde(skip)]
+    id: usize,
+}
+
+fn html(input: Input<Dry>) -> String {
+    input.freeze().to_string()
+}
+
+#[test]
+fn inputs() {
+    let PersonForm {
+        first_name,
+        age,
+        nickname,
+        subscribed,
+        address: AddressForm { street },
+    } = Person::form::<Dry>();
+
+    assert_eq!(html(first_name), r#"<input name="firstName" type="text">"#);
+    assert_eq!(html(age), r#"<input name="years" type="number">"#);
+    assert_eq!(html(nickname), r#"<input name="nickname" type="text">"#);
+    assert_eq!(
+        html(subscribed),
+        r#"<input name="subscribed" type="checkbox" value="true">"#
+    );
+    assert_eq!(html(street), r#"<input name="street_address" type="text">"#);
+}
+
+#[test]
+fn skipped_inputs() {
+    let SkippedForm { .. } = Skipped::form::<Dry>();
+}
+
+// Form data can't deserialize numbers or bools inside a flattened
+// struct, so this doesn't use `Person`.
+#[derive(Deserialize, HtmxForm, Debug, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct Account {
+    user_name: String,
+    age: Option<u32>,
+    #[serde(default)]
+    subscribed: bool,
+}
+
+#[test]
+fn kebab_case_inputs() {
+    let AccountForm {
+        user_name,
+        age,
+        subscribed,
+    } = Account::form::<Dry>();
+
+    assert_eq!(html(user_name), r#"<input name="user-name" type="text">"#);
+    assert_eq!(html(age), r#"<input name="age" type="number">"#);
+    assert_eq!(
+        html(subscribed),
+        r#"<input name="subscribed" type="checkbox" value="true">"#
+    );
+}
+
+#[tokio::test]
+async fn submit() {
+    assert_eq!(
+        post("user-name=ada&age=36&subscribed=true").await,
+        Account {
+            user_name: "ada".to_string(),
+            age: Some(36),
+            subscribed: true,
+        }
+    );
+}
+
+#[tokio::test]
+async fn submit_unchecked() {
+    assert_eq!(
+        post("user-name=ada").await,
+        Account {
+            user_name: "ada".to_string(),
+            age: None,
+            subscribed: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn submit_blank_optional() {
+    assert_eq!(
+        post("user-name=ada&age=").await,
+        Account {
+            user_name: "ada".to_string(),
+            age: None,
+            subscribed: false,
+        }
+    );
+}
+
+async fn post(body: &'static str) -> Account {
+    let request = Request::builder().body(Body::from(body)).unwrap();
+    let HtmxPostRequest(account) = HtmxPostRequest::from_request(request, &()).await.unwrap();
+    account
+}
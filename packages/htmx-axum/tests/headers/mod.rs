@@ -0,0 +1,106 @@
+use axum::{
+    body::Body,
+    extract::{FromRequest, FromRequestParts},
+    http::{request::Parts, Request, StatusCode},
+};
+use serde::Deserialize;
+use silkenweb_htmx_axum::{
+    HtmxPostRequest, HxBoosted, HxCurrentUrl, HxRequest, HxTarget, HxTrigger,
+};
+
+fn parts(headers: &[(&str, &str)]) -> Parts {
+    let mut request = Request::builder();
+
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    request.body(()).unwrap().into_parts().0
+}
+
+#[tokio::test]
+async fn flag_headers() {
+    let mut htmx = parts(&[("HX-Request", "true"), ("HX-Boosted", "false")]);
+    let mut plain = parts(&[]);
+
+    assert!(
+        HxRequest::from_request_parts(&mut htmx, &())
+            .await
+            .unwrap()
+            .0
+    );
+    assert!(
+        !HxBoosted::from_request_parts(&mut htmx, &())
+            .await
+            .unwrap()
+            .0
+    );
+    assert!(
+        !HxRequest::from_request_parts(&mut plain, &())
+            .await
+            .unwrap()
+            .0
+    );
+}
+
+#[tokio::test]
+async fn text_headers() {
+    let mut htmx = parts(&[
+        ("HX-Target", "results"),
+        ("HX-Current-URL", "https://example.com/search"),
+    ]);
+
+    assert_eq!(
+        HxTarget::from_request_parts(&mut htmx, &())
+            .await
+            .unwrap()
+            .0,
+        Some("results".to_string())
+    );
+    assert_eq!(
+        HxCurrentUrl::from_request_parts(&mut htmx, &())
+            .await
+            .unwrap()
+            .0,
+        Some("https://example.com/search".to_string())
+    );
+    assert_eq!(
+        HxTrigger::from_request_parts(&mut htmx, &())
+            .await
+            .unwrap()
+            .0,
+        None
+    );
+}
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+struct Name {
+    first: String,
+    last: String,
+}
+
+#[tokio::test]
+async fn post_request() {
+    let request = Request::builder()
+        .body(Body::from("first=Ada&last=Lovelace"))
+        .unwrap();
+    let HtmxPostRequest(name) = HtmxPostRequest::<Name>::from_request(request, &())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        name,
+        Name {
+            first: "Ada".to_string(),
+            last: "Lovelace".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn invalid_post_request() {
+    let request = Request::builder().body(Body::from("first=Ada")).unwrap();
+    let rejection = HtmxPostRequest::<Name>::from_request(request, &()).await;
+
+    assert_eq!(rejection.err(), Some(StatusCode::BAD_REQUEST));
+}
@@ -0,0 +1,16 @@
+use axum::{body::HttpBody, response::Response};
+
+mod form;
+mod headers;
+mod response;
+
+async fn body_text(response: Response) -> String {
+    let mut body = response.into_body();
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk.unwrap());
+    }
+
+    String::from_utf8(bytes).unwrap()
+}
@@ -0,0 +1,87 @@
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use silkenweb::{
+    dom::Dry,
+    elements::html::{div, p, Div},
+    prelude::{HtmlElement, ParentElement},
+};
+use silkenweb_htmx_axum::{HtmxResponse, Swap};
+
+use crate::body_text;
+
+fn content() -> Div<Dry> {
+    div().id("content").child(p().text("Hello, world!"))
+}
+
+#[tokio::test]
+async fn body() {
+    let response = HtmxResponse::new(content()).into_response();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    assert_eq!(
+        body_text(response).await,
+        r#"<div id="content"><p>Hello, world!</p></div>"#
+    );
+}
+
+#[tokio::test]
+async fn out_of_band() {
+    let response = HtmxResponse::new(content())
+        .out_of_band(Swap::OuterHtml, div().id("count").text("1"))
+        .out_of_band(Swap::BeforeEnd, div().id("log").text("Updated"))
+        .into_response();
+
+    assert_eq!(
+        body_text(response).await,
+        concat!(
+            r#"<div id="content"><p>Hello, world!</p></div>"#,
+            r#"<div id="count" hx-swap-oob="outerHTML">1</div>"#,
+            r#"<div id="log" hx-swap-oob="beforeend">Updated</div>"#
+        )
+    );
+}
+
+#[tokio::test]
+async fn headers() {
+    let response = HtmxResponse::new(content())
+        .push_url("/page/2")
+        .reswap(Swap::InnerHtml)
+        .trigger("first")
+        .trigger("second")
+        .into_response();
+    let headers = response.headers();
+
+    assert_eq!(headers["hx-push-url"], "/page/2");
+    assert_eq!(headers["hx-reswap"], "innerHTML");
+    assert_eq!(headers["hx-trigger"], r#"{"first":null,"second":null}"#);
+}
+
+#[tokio::test]
+async fn repeated_headers() {
+    let response = HtmxResponse::new(content())
+        .push_url("/first")
+        .push_url("/second")
+        .into_response();
+    let urls: Vec<_> = response.headers().get_all("hx-push-url").iter().collect();
+
+    assert_eq!(urls, ["/second"], "The last value replaces the others");
+}
+
+#[tokio::test]
+async fn invalid_header() {
+    let response = HtmxResponse::new(content())
+        .redirect("/new\nline")
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        body_text(response).await,
+        r#"Invalid value for `hx-redirect` header: "/new\nline""#
+    );
+}
@@ -1,17 +1,23 @@
-use parse::Transpile;
+use std::{env, iter};
+
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use proc_macro_error::{abort, abort_call_site, proc_macro_error};
+use proc_macro_error::{
+    abort, abort_call_site, abort_if_dirty, emit_error, proc_macro_error,
+};
 use quote::quote;
 use silkenweb_base::css::{self, Source};
 use syn::{
     parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed,
-    FieldsUnnamed, Ident, Index, LitBool, Meta, NestedMeta,
+    FieldsUnnamed, Ident, Index, ItemFn, LitBool, LitStr, Meta, NestedMeta,
 };
 
 use crate::parse::Input;
 
 mod parse;
+mod styled;
+mod template;
+mod view;
 
 macro_rules! derive_empty(
     (
@@ -140,6 +146,14 @@ pub fn derive_element(item: TokenStream) -> TokenStream {
                 Self{#target: self.#target.attribute(name, value) #other_fields}
             }
 
+            fn style_property<'a>(
+                mut self,
+                name: &str,
+                value: impl ::silkenweb::value::RefSignalOrValue<'a, Item = impl ::silkenweb::attribute::Attribute>,
+            ) -> Self {
+                Self{#target: self.#target.style_property(name, value) #other_fields}
+            }
+
             fn effect(self, f: impl FnOnce(&Self::DomType) + 'static) -> Self {
                 Self{#target: self.#target.effect(f) #other_fields}
             }
@@ -163,6 +177,26 @@ pub fn derive_element(item: TokenStream) -> TokenStream {
             fn on(self, name: &'static str, f: impl FnMut(::silkenweb::macros::JsValue) + 'static) -> Self {
                 Self{#target: self.#target.on(name, f) #other_fields}
             }
+
+            fn on_mount(self, f: impl FnMut(&Self::DomType) + 'static) -> Self {
+                Self{#target: self.#target.on_mount(f) #other_fields}
+            }
+
+            fn on_unmount(self, f: impl FnMut(&Self::DomType) + 'static) -> Self {
+                Self{#target: self.#target.on_unmount(f) #other_fields}
+            }
+
+            fn on_cleanup(self, f: impl FnOnce() + 'static) -> Self {
+                Self{#target: self.#target.on_cleanup(f) #other_fields}
+            }
+
+            fn provide_context<T: 'static>(self, value: T) -> Self {
+                Self{#target: self.#target.provide_context(value) #other_fields}
+            }
+
+            fn use_context<T: Clone + 'static>(&self) -> ::silkenweb::context::ContextSignal<T> {
+                self.#target.use_context()
+            }
         }
     )
     .into()
@@ -270,143 +304,445 @@ pub fn cfg_browser(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn css(input: TokenStream) -> TokenStream {
     let Input {
         mut source,
+        content_literal,
         public,
         prefix,
         include_prefixes,
         exclude_prefixes,
         validate,
         auto_mount,
+        scoped,
+        warn_unused,
+        export_vars,
+        export_animations,
         transpile,
     } = parse_macro_input!(input);
 
-    let name_mappings = source
-        .transpile(validate, transpile.map(Transpile::into))
-        .unwrap_or_else(|e| abort_call_site!(e));
+    let class_ident = |class: &str| -> Option<String> {
+        let include = include_prefixes.as_ref().map_or(true, |include_prefixes| {
+            any_prefix_matches(class, include_prefixes)
+        });
+        let exclude = any_prefix_matches(class, &exclude_prefixes);
 
-    let class_names: Vec<(String, String)> = if let Some(name_mappings) = name_mappings {
-        name_mappings
-            .into_iter()
-            .map(|(class_ident, class)| {
-                if !class.composes.is_empty() {
-                    abort_call_site!("`composes` is unsupported");
-                }
+        if !include || exclude {
+            return None;
+        }
 
-                (class_ident, class.name)
-            })
-            .collect()
-    } else {
-        css::class_names(&source)
-            .map(|class| (class.clone(), class))
-            .collect()
+        match &prefix {
+            Some(prefix) => class.strip_prefix(prefix).map(str::to_string),
+            None => Some(class.to_string()),
+        }
     };
 
-    let classes = class_names.into_iter().filter(|(class_ident, _css_class)| {
-        let include = if let Some(include_prefixes) = include_prefixes.as_ref() {
-            any_prefix_matches(class_ident, include_prefixes)
+    let scope = scoped.then(|| source.scope());
+    let tree_shake = transpile.as_ref().map_or(false, |t| t.tree_shake);
+    let mut transpile: Option<css::Transpile> = transpile.map(|t| t.options);
+    let mut warning = None;
+
+    if tree_shake || warn_unused {
+        let used = css::used_classes().unwrap_or_else(|e| abort_call_site!(e));
+        let composed: Vec<String> = css::composed_classes(&source).collect();
+        let mut unused: Vec<String> = css::class_names(&source)
+            .filter(|class| {
+                !composed.contains(class)
+                    && class_ident(class).map_or(false, |ident| {
+                        ident.starts_with(char::is_alphabetic)
+                            && !used.contains(&rust_ident(&ident, auto_mount))
+                    })
+            })
+            .collect();
+        unused.sort();
+
+        if warn_unused && !unused.is_empty() {
+            warning = Some(format!("Unused CSS classes: {}", unused.join(", ")));
+        }
+
+        if let Some(transpile) = transpile.as_mut().filter(|_| tree_shake) {
+            transpile.unused_classes = unused.into_iter().collect();
+        }
+    }
+
+    // Classes, custom properties and keyframes are separate namespaces, so a
+    // class can have the same name as a keyframes rule.
+    let classes: Vec<String> = css::class_names(&source).collect();
+    let custom_properties: Vec<String> = css::custom_properties(&source).collect();
+    let keyframes: Vec<String> = css::keyframes(&source).collect();
+    let name_mappings = source
+        .transpile(validate, transpile.clone())
+        .unwrap_or_else(|errors| {
+            abort_transpile_errors(
+                &errors,
+                &source,
+                content_literal.as_ref(),
+                Span::call_site(),
+            )
+        });
+    let mut dependencies = Vec::new();
+
+    let (class_names, variables, animations): (Vec<_>, Vec<_>, Vec<_>) =
+        if let (Some(name_mappings), Some(transpile)) = (name_mappings, transpile) {
+            let mut composes = css::Composes::new(validate, transpile);
+            let class_names = classes
+                .into_iter()
+                // Classes removed by `tree_shake` have no mapping.
+                .filter(|class_ident| name_mappings.contains_key(class_ident))
+                .map(|class_ident| {
+                    let class_list = composes
+                        .class_list(&source, &name_mappings, &class_ident)
+                        .unwrap_or_else(|e| abort_call_site!(e));
+
+                    (class_ident, class_list)
+                })
+                .collect();
+            dependencies.extend(composes.dependencies().cloned());
+            let mapped_name = |name: String| {
+                let mapped = name_mappings
+                    .get(&name)
+                    .map_or_else(|| name.clone(), |export| export.name.clone());
+                (name, mapped)
+            };
+
+            (
+                class_names,
+                custom_properties.into_iter().map(mapped_name).collect(),
+                keyframes.into_iter().map(mapped_name).collect(),
+            )
         } else {
-            true
+            let unmapped_name = |name: String| (name.clone(), name);
+
+            (
+                css::class_names(&source).map(unmapped_name).collect(),
+                custom_properties.into_iter().map(unmapped_name).collect(),
+                keyframes.into_iter().map(unmapped_name).collect(),
+            )
         };
+    // Unlike classes, we skip any names that aren't valid identifiers, as they're
+    // often used by 3rd party stylesheets for private custom properties.
+    let valid_ident = |(ident, _name): &(String, String)| ident.starts_with(char::is_alphabetic);
+    let variables = variables
+        .into_iter()
+        .map(|(ident, name)| (ident.strip_prefix("--").unwrap_or(&ident).to_string(), name))
+        .filter(valid_ident);
+
+    let classes = exported_items(
+        auto_mount,
+        class_names
+            .into_iter()
+            .filter_map(|(class, css_class)| class_ident(&class).map(|ident| (ident, css_class))),
+    );
 
-        let exclude = any_prefix_matches(class_ident, &exclude_prefixes);
+    let extract_dir = env::var_os(EXTRACT_DIR_VAR);
 
-        include && !exclude
-    });
+    if let Some(extract_dir) = &extract_dir {
+        for stylesheet in dependencies.iter().chain(iter::once(&source)) {
+            stylesheet
+                .extract(extract_dir)
+                .unwrap_or_else(|e| abort_call_site!(e));
+        }
+    }
 
-    if let Some(prefix) = prefix {
-        code_gen(
-            &source,
-            public,
-            auto_mount,
-            classes.filter_map(|(class_ident, css_class)| {
-                let class_ident = class_ident.strip_prefix(&prefix).map(str::to_string);
-                class_ident.map(|class_ident| (class_ident, css_class))
+    code_gen(
+        &source,
+        &dependencies,
+        scope.as_deref(),
+        extract_dir.is_some(),
+        public,
+        warning.as_deref(),
+        Exports {
+            classes,
+            variables: export_vars.then(|| exported_items(auto_mount, variables)),
+            animations: export_animations.then(|| {
+                exported_items(auto_mount, animations.into_iter().filter(valid_ident))
             }),
-        )
-    } else {
-        code_gen(&source, public, auto_mount, classes)
+        },
+    )
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn styled(input: TokenStream) -> TokenStream {
+    let styled: styled::Styled = parse_macro_input!(input);
+    styled.expand().into()
+}
+
+#[proc_macro]
+#[proc_macro_error]
+pub fn view(input: TokenStream) -> TokenStream {
+    let view: view::View = parse_macro_input!(input);
+    view.expand().into()
+}
+
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn template(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        abort_call_site!("Expected no arguments");
     }
+
+    let item: ItemFn = parse_macro_input!(item);
+    template::Template::new(item).expand().into()
 }
 
-fn any_prefix_matches(x: &str, prefixes: &[String]) -> bool {
-    prefixes.iter().any(|prefix| x.starts_with(prefix))
+const EXTRACT_DIR_VAR: &str = "SILKENWEB_CSS_EXTRACT_DIR";
+
+/// Items exported from a stylesheet.
+///
+/// `variables` and `animations` are `None` unless they were asked for, as their
+/// modules are more likely to clash with the user's items.
+struct Exports {
+    classes: Vec<proc_macro2::TokenStream>,
+    variables: Option<Vec<proc_macro2::TokenStream>>,
+    animations: Option<Vec<proc_macro2::TokenStream>>,
 }
 
-fn code_gen(
+/// Emit an error for each of `errors`, pointing into the CSS literal if we can,
+/// or at `span` otherwise, and abort.
+fn abort_transpile_errors(
+    errors: &[css::TranspileError],
     source: &Source,
-    public: bool,
-    auto_mount: bool,
-    classes: impl Iterator<Item = (String, String)>,
-) -> TokenStream {
-    let classes = classes.map(|(class_ident, class_name)| {
-        if !class_ident.starts_with(char::is_alphabetic) {
-            abort_call_site!(
-                "Identifier '{}' doesn't start with an alphabetic character",
-                class_ident
-            );
+    literal: Option<&LitStr>,
+    span: Span,
+) -> ! {
+    for error in errors {
+        let location = match &error.location {
+            Some(location) => location,
+            None => {
+                emit_error!(span, "{}", error.message);
+                continue;
+            }
+        };
+
+        // Offsets into the literal are only meaningful if we haven't rewritten the
+        // CSS, for example to scope it.
+        let literal = literal.filter(|literal| literal.value() == source.content());
+        let span = literal
+            .and_then(|literal| literal_span(literal, location.line, location.column))
+            .or_else(|| literal.map(LitStr::span))
+            .unwrap_or(span);
+        let mut message = format!(
+            "{}\n --> {}:{}:{}",
+            error.message, location.filename, location.line, location.column
+        );
+
+        if let Some(line) = source.content().lines().nth(location.line as usize - 1) {
+            let indent = " ".repeat(location.column.saturating_sub(1) as usize);
+            message.push_str(&format!("\n  | {line}\n  | {indent}^"));
         }
 
-        let class_ident = class_ident.replace(|c: char| !c.is_alphanumeric(), "_");
+        if location.filename.ends_with(".scss") || location.filename.ends_with(".sass") {
+            message.push_str("\nnote: the location is in the CSS compiled from Sass");
+        }
 
-        if auto_mount {
-            let class_ident = Ident::new(&class_ident.to_lowercase(), Span::call_site());
-            quote!(pub fn #class_ident() -> &'static str {
-                use ::std::{panic::Location, sync::Once};
+        emit_error!(span, "{}", message);
+    }
 
-                static INIT: Once = Once::new();
+    abort_if_dirty();
+    unreachable!("Expected at least one error")
+}
 
-                INIT.call_once(|| {
-                    super::stylesheet::mount()
-                });
+/// The span of the character at `line` and `column` (both starting at 1) in
+/// the value of `literal`.
+///
+/// This is only available if `literal` has no escapes, so offsets in its value
+/// and source match.
+fn literal_span(literal: &LitStr, line: u32, column: u32) -> Option<Span> {
+    let token = literal.token().to_string();
+    let body_start = token.find('"')? + 1;
+    let body_end = token.rfind('"')?;
+    let body = token.get(body_start..body_end)?;
+
+    if body != literal.value() {
+        return None;
+    }
 
-                #class_name
-            })
-        } else {
-            let class_ident = Ident::new(&class_ident.to_uppercase(), Span::call_site());
-            quote!(pub const #class_ident: &str = #class_name;)
-        }
-    });
+    let line_start: usize = body
+        .split_inclusive('\n')
+        .take(line as usize - 1)
+        .map(str::len)
+        .sum();
+    let (column_offset, ch) = body[line_start..].char_indices().nth(column as usize - 1)?;
+    let start = body_start + line_start + column_offset;
+
+    literal.token().subspan(start..start + ch.len_utf8())
+}
+
+fn any_prefix_matches(x: &str, prefixes: &[String]) -> bool {
+    prefixes.iter().any(|prefix| x.starts_with(prefix))
+}
 
-    let dependency = source.dependency().iter();
+fn code_gen(
+    source: &Source,
+    dependencies: &[Source],
+    scope: Option<&str>,
+    extracted: bool,
+    public: bool,
+    warning: Option<&str>,
+    Exports {
+        classes,
+        variables,
+        animations,
+    }: Exports,
+) -> TokenStream {
+    let dependency = iter::once(source)
+        .chain(dependencies)
+        .flat_map(|source| source.dependency().iter().chain(source.imports()));
     let content = source.content();
+    let composed_ids = dependencies.iter().map(|source| {
+        format!(
+            "silkenweb-style:{}",
+            source.dependency().as_deref().unwrap_or_default()
+        )
+    });
+    let composed_content = dependencies.iter().map(Source::content);
     let visibility = if public { quote!(pub) } else { quote!() };
+    // Extracted stylesheets are bundled by a build step, so there's nothing to
+    // mount.
+    let mount = (!extracted).then(|| {
+        quote!(
+            use ::std::panic::Location;
+            use ::silkenweb::{
+                document::Document,
+                dom::DefaultDom,
+                node::element::ParentElement,
+                elements::html::style,
+            };
+
+            #(DefaultDom::mount_in_head(#composed_ids, style().text(#composed_content));)*
+
+            let location = Location::caller();
+            DefaultDom::mount_in_head(
+                &format!(
+                    "silkenweb-style:{}:{}:{}",
+                    location.file(),
+                    location.line(),
+                    location.column()
+                ),
+                style().text(text())
+            );
+        )
+    });
+    let scoped = scope.map(|scope| {
+        quote!(
+            /// The attribute that marks elements in this stylesheet's scope.
+            pub const SCOPE: &str = #scope;
+
+            /// Mount the stylesheet and call `f`, marking any elements it
+            /// creates with [`SCOPE`].
+            pub fn scoped<T>(f: impl FnOnce() -> T) -> T {
+                mount();
+                ::silkenweb::style::scoped(SCOPE, f)
+            }
+        )
+    });
+
+    let variables = variables.map(|variables| {
+        quote!(
+            #visibility mod var {
+                #(#variables)*
+            }
+        )
+    });
+    let animations = animations.map(|animations| {
+        quote!(
+            #visibility mod animation {
+                #(#animations)*
+            }
+        )
+    });
+
+    let warning = warning.map(call_site_warning);
 
     quote!(
         #(const _: &[u8] = ::std::include_bytes!(#dependency);)*
+        #warning
+        // Let cargo know the output depends on this, so changing it rebuilds the crate.
+        const _: ::std::option::Option<&str> = ::std::option_env!(#EXTRACT_DIR_VAR);
 
         #visibility mod class {
             #(#classes)*
         }
 
+        #variables
+        #animations
+
         #visibility mod stylesheet {
             pub fn mount() {
-                use ::std::panic::Location;
-                use silkenweb::{
-                    document::Document,
-                    dom::DefaultDom,
-                    node::element::ParentElement,
-                    elements::html::style,
-                };
-
-                let location = Location::caller();
-                DefaultDom::mount_in_head(
-                    &format!(
-                        "silkenweb-style:{}:{}:{}",
-                        location.file(),
-                        location.line(),
-                        location.column()
-                    ),
-                    style().text(text())
-                );
+                #mount
             }
 
             pub fn text() -> &'static str {
                 #content
             }
+
+            #scoped
         }
     )
     .into()
 }
 
+/// Code that makes the compiler warn with `message` at the call site.
+///
+/// `proc_macro_error`'s warnings only work on nightly, so this uses the
+/// deprecation lint, which works on stable.
+fn call_site_warning(message: &str) -> proc_macro2::TokenStream {
+    quote!(
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct Warning;
+
+            let _ = Warning;
+        };
+    )
+}
+
+/// Generate a constant, or a function if `auto_mount` is set, for each
+/// `(identifier, name)` pair.
+fn exported_items(
+    auto_mount: bool,
+    items: impl Iterator<Item = (String, String)>,
+) -> Vec<proc_macro2::TokenStream> {
+    items
+        .map(|(ident, name)| {
+            if !ident.starts_with(char::is_alphabetic) {
+                abort_call_site!(
+                    "Identifier '{}' doesn't start with an alphabetic character",
+                    ident
+                );
+            }
+
+            let ident = Ident::new(&rust_ident(&ident, auto_mount), Span::call_site());
+
+            if auto_mount {
+                quote!(pub fn #ident() -> &'static str {
+                    use ::std::{panic::Location, sync::Once};
+
+                    static INIT: Once = Once::new();
+
+                    INIT.call_once(|| {
+                        super::stylesheet::mount()
+                    });
+
+                    #name
+                })
+            } else {
+                quote!(pub const #ident: &str = #name;)
+            }
+        })
+        .collect()
+}
+
+/// The Rust identifier for a CSS identifier. This is a function name if
+/// `auto_mount` is set, otherwise a constant name.
+fn rust_ident(css_ident: &str, auto_mount: bool) -> String {
+    let ident = css_ident.replace(|c: char| !c.is_alphanumeric(), "_");
+
+    if auto_mount {
+        ident.to_lowercase()
+    } else {
+        ident.to_uppercase()
+    }
+}
+
 /// Convert a rust ident to an html ident by stripping any "r#" prefix and
 /// replacing '_' with '-'.
 #[doc(hidden)]
@@ -419,3 +755,16 @@ pub fn rust_to_html_ident(input: TokenStream) -> TokenStream {
 
     quote!(#html_ident_name).into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::rust_ident;
+
+    #[test]
+    fn rust_idents() {
+        assert_eq!(rust_ident("my-class", false), "MY_CLASS");
+        assert_eq!(rust_ident("my-class", true), "my_class");
+        assert_eq!(rust_ident("size-1.5", false), "SIZE_1_5");
+        assert_eq!(rust_ident("hover:bg", true), "hover_bg");
+    }
+}
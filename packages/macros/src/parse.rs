@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use derive_more::Into;
 use proc_macro_error::{abort, abort_call_site};
 use silkenweb_base::css::{self, Source};
@@ -19,11 +21,18 @@ mod kw {
     custom_keyword!(exclude_prefixes);
     custom_keyword!(validate);
     custom_keyword!(auto_mount);
+    custom_keyword!(scoped);
+    custom_keyword!(warn_unused);
+    custom_keyword!(export_vars);
+    custom_keyword!(export_animations);
     custom_keyword!(transpile);
     custom_keyword!(minify);
     custom_keyword!(pretty);
     custom_keyword!(modules);
     custom_keyword!(nesting);
+    custom_keyword!(dashed_idents);
+    custom_keyword!(tree_shake);
+    custom_keyword!(source_map);
     custom_keyword!(browsers);
 }
 
@@ -52,6 +61,31 @@ impl ParseValue for String {
     }
 }
 
+/// Inline CSS content, and the literal it came from, if it's a single string
+/// literal.
+pub struct Content {
+    value: String,
+    literal: Option<LitStr>,
+}
+
+impl ParseValue for Content {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let literal: LitStr = input.parse()?;
+
+            Ok(Self {
+                value: literal.value(),
+                literal: Some(literal),
+            })
+        } else {
+            Ok(Self {
+                value: String::parse(input)?,
+                literal: None,
+            })
+        }
+    }
+}
+
 impl<T: ParseValue> ParseValue for Vec<T> {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let list;
@@ -62,12 +96,17 @@ impl<T: ParseValue> ParseValue for Vec<T> {
 
 pub struct Input {
     pub source: Source,
+    pub content_literal: Option<LitStr>,
     pub public: bool,
     pub prefix: Option<String>,
     pub include_prefixes: Option<Vec<String>>,
     pub exclude_prefixes: Vec<String>,
     pub validate: bool,
     pub auto_mount: bool,
+    pub scoped: bool,
+    pub warn_unused: bool,
+    pub export_vars: bool,
+    pub export_animations: bool,
     pub transpile: Option<Transpile>,
 }
 
@@ -77,24 +116,33 @@ impl Parse for Input {
             return Ok(Self {
                 source: Source::from_path(input.parse::<LitStr>()?.value())
                     .unwrap_or_else(|e| abort_call_site!(e)),
+                content_literal: None,
                 public: false,
                 prefix: None,
                 include_prefixes: None,
                 exclude_prefixes: Vec::new(),
                 validate: false,
                 auto_mount: false,
+                scoped: false,
+                warn_unused: false,
+                export_vars: false,
+                export_animations: false,
                 transpile: None,
             });
         }
 
         let mut path: Option<String> = None;
         let mut public = false;
-        let mut content: Option<String> = None;
+        let mut content: Option<Content> = None;
         let mut prefix = None;
         let mut include_prefixes = None;
         let mut exclude_prefixes = None;
         let mut validate = false;
         let mut auto_mount = false;
+        let mut scoped = false;
+        let mut warn_unused = false;
+        let mut export_vars = false;
+        let mut export_animations = false;
         let mut transpile = None;
 
         parse_fields(input, |field, input| {
@@ -106,13 +154,20 @@ impl Parse for Input {
                 || parameter(kw::exclude_prefixes, field, input, &mut exclude_prefixes)?
                 || flag(kw::validate, field, input, &mut validate)?
                 || flag(kw::auto_mount, field, input, &mut auto_mount)?
+                || flag(kw::scoped, field, input, &mut scoped)?
+                || flag(kw::warn_unused, field, input, &mut warn_unused)?
+                || flag(kw::export_vars, field, input, &mut export_vars)?
+                || flag(kw::export_animations, field, input, &mut export_animations)?
                 || parameter(kw::transpile, field, input, &mut transpile)?)
         })?;
 
-        let source = match (path, content) {
+        let (source, content_literal) = match (path, content) {
             (None, None) => abort_call_site!("Must specify either 'path' or `content` parameter"),
-            (None, Some(content)) => Source::from_content(content),
-            (Some(path), None) => Source::from_path(path).unwrap_or_else(|e| abort_call_site!(e)),
+            (None, Some(content)) => (Source::from_content(content.value), content.literal),
+            (Some(path), None) => (
+                Source::from_path(path).unwrap_or_else(|e| abort_call_site!(e)),
+                None,
+            ),
             (Some(_), Some(_)) => {
                 abort_call_site!("Only one of 'path' or `content` can be specified")
             }
@@ -120,19 +175,26 @@ impl Parse for Input {
 
         Ok(Self {
             source,
+            content_literal,
             public,
             prefix,
             include_prefixes,
             exclude_prefixes: exclude_prefixes.unwrap_or_default(),
             validate,
             auto_mount,
+            scoped,
+            warn_unused,
+            export_vars,
+            export_animations,
             transpile,
         })
     }
 }
 
-#[derive(Into)]
-pub struct Transpile(css::Transpile);
+pub struct Transpile {
+    pub options: css::Transpile,
+    pub tree_shake: bool,
+}
 
 impl ParseValue for Transpile {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -140,6 +202,9 @@ impl ParseValue for Transpile {
         let mut pretty = false;
         let mut modules = false;
         let mut nesting = false;
+        let mut dashed_idents = false;
+        let mut tree_shake = false;
+        let mut source_map = false;
         let mut browsers = None;
 
         parse_fields(&parenthesized(input)?, |field, input| {
@@ -147,16 +212,25 @@ impl ParseValue for Transpile {
                 || flag(kw::pretty, field, input, &mut pretty)?
                 || flag(kw::modules, field, input, &mut modules)?
                 || flag(kw::nesting, field, input, &mut nesting)?
+                || flag(kw::dashed_idents, field, input, &mut dashed_idents)?
+                || flag(kw::tree_shake, field, input, &mut tree_shake)?
+                || flag(kw::source_map, field, input, &mut source_map)?
                 || parameter(kw::browsers, field, input, &mut browsers)?)
         })?;
 
-        Ok(Self(css::Transpile {
-            minify,
-            pretty,
-            modules,
-            nesting,
-            browsers: browsers.map(Browsers::into),
-        }))
+        Ok(Self {
+            options: css::Transpile {
+                minify,
+                pretty,
+                modules,
+                nesting,
+                dashed_idents,
+                unused_classes: HashSet::new(),
+                source_map,
+                browsers: browsers.map(Browsers::into),
+            },
+            tree_shake,
+        })
     }
 }
 
@@ -0,0 +1,278 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    iter::Peekable,
+};
+
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use proc_macro_error::abort;
+use quote::quote;
+use silkenweb_base::css::{Source, Transpile};
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, Token,
+};
+
+use crate::abort_transpile_errors;
+
+pub struct Styled {
+    element: Expr,
+    content: Group,
+}
+
+impl Parse for Styled {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let element = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let content: Group = input.parse()?;
+
+        if content.delimiter() != Delimiter::Brace {
+            abort!(content, "Expected `{{ ... }}`");
+        }
+
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self { element, content })
+    }
+}
+
+impl Styled {
+    pub fn expand(self) -> TokenStream {
+        let mut hasher = DefaultHasher::new();
+        self.content.stream().to_string().hash(&mut hasher);
+        let class = format!("silkenweb-styled-{:x}", hasher.finish() as u32);
+        let var_name = |index: usize| format!("--{class}-{index}");
+
+        let mut values = Vec::new();
+        collect_values(self.content.stream(), &mut values);
+        let rules = source_text(&self.content, &values, var_name)
+            .unwrap_or_else(|| render(self.content.stream(), &mut 0, var_name));
+
+        let mut source = Source::from_content(format!(".{class} {{ {rules} }}"));
+        source
+            .transpile(
+                true,
+                Some(Transpile {
+                    minify: true,
+                    pretty: false,
+                    modules: false,
+                    nesting: true,
+                    dashed_idents: false,
+                    unused_classes: Default::default(),
+                    source_map: false,
+                    browsers: None,
+                }),
+            )
+            .unwrap_or_else(|errors| {
+                abort_transpile_errors(&errors, &source, None, self.content.span())
+            });
+
+        let stylesheet = source.content();
+        let id = format!("silkenweb-style:{class}");
+        let element = self.element;
+        let var_names = (0..values.len()).map(var_name);
+        let values = values.into_iter().map(|(_group, value)| value);
+
+        quote!({
+            static MOUNT: ::std::sync::Once = ::std::sync::Once::new();
+
+            MOUNT.call_once(|| {
+                <::silkenweb::dom::DefaultDom as ::silkenweb::document::Document>::mount_in_head(
+                    #id,
+                    ::silkenweb::node::element::ParentElement::text(
+                        ::silkenweb::elements::html::style(),
+                        #stylesheet,
+                    ),
+                );
+            });
+
+            let element = ::silkenweb::node::element::Element::class(#element, #class);
+            #(
+                let element = ::silkenweb::node::element::Element::style_property(
+                    element,
+                    #var_names,
+                    #values,
+                );
+            )*
+            element
+        })
+    }
+}
+
+/// Collect each `${ value }` interpolation in `tokens`, in the order they
+/// appear.
+fn collect_values(tokens: TokenStream, values: &mut Vec<(Group, Expr)>) {
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        if let Some(group) = interpolation(&token, &mut tokens) {
+            let value = syn::parse2(group.stream()).unwrap_or_else(|e| abort!(e.span(), "{}", e));
+            values.push((group, value));
+        } else if let TokenTree::Group(group) = token {
+            collect_values(group.stream(), values);
+        }
+    }
+}
+
+/// If `token` starts an interpolation, consume and return its `{ value }`
+/// group.
+fn interpolation(
+    token: &TokenTree,
+    tokens: &mut Peekable<impl Iterator<Item = TokenTree>>,
+) -> Option<Group> {
+    match (token, tokens.peek()) {
+        (TokenTree::Punct(dollar), Some(TokenTree::Group(group)))
+            if dollar.as_char() == '$' && group.delimiter() == Delimiter::Brace =>
+        {
+            let group = group.clone();
+            tokens.next();
+            Some(group)
+        }
+        _ => None,
+    }
+}
+
+/// The CSS from the source text of `content`, with each interpolation replaced
+/// by its custom property.
+///
+/// This preserves the original whitespace, which CSS is sensitive to, so it's
+/// preferred over [`render`]. It's only available if the tokens come from real
+/// source code.
+fn source_text(
+    content: &Group,
+    values: &[(Group, Expr)],
+    var_name: impl Fn(usize) -> String,
+) -> Option<String> {
+    let text = content.span().source_text()?;
+    let mut remaining = text.strip_prefix('{')?.strip_suffix('}')?;
+    let mut css = String::new();
+
+    for (index, (group, _value)) in values.iter().enumerate() {
+        let group_text = group.span().source_text()?;
+
+        loop {
+            let dollar = remaining.find('$')?;
+            css.push_str(&remaining[..dollar]);
+            remaining = &remaining[dollar + 1..];
+            let after_whitespace = remaining.trim_start();
+
+            if let Some(rest) = after_whitespace.strip_prefix(&group_text) {
+                css.push_str(&format!("var({})", var_name(index)));
+                remaining = rest;
+                break;
+            }
+
+            css.push('$');
+        }
+    }
+
+    css.push_str(remaining);
+
+    Some(css)
+}
+
+/// Render `tokens` as CSS, with each interpolation replaced by its custom
+/// property.
+///
+/// Whitespace isn't available from tokens, so punctuation is joined to the
+/// tokens either side of it, as are parenthesized and bracketed groups to the
+/// preceding token. This is correct for most CSS, like `margin-top: 1.5rem;`.
+/// The exception is punctuation followed by more punctuation, where the
+/// token's spacing tells us if there was whitespace between them, so
+/// selectors like `& .child` and `&.active` are both rendered correctly.
+fn render(
+    tokens: TokenStream,
+    index: &mut usize,
+    var_name: impl Fn(usize) -> String + Copy,
+) -> String {
+    let mut css = String::new();
+    let mut join_next = true;
+    let mut space_next = false;
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        if interpolation(&token, &mut tokens).is_some() {
+            if space_next || !join_next {
+                css.push(' ');
+            }
+
+            css.push_str(&format!("var({})", var_name(*index)));
+            *index += 1;
+            join_next = false;
+            space_next = false;
+            continue;
+        }
+
+        let is_punct = matches!(token, TokenTree::Punct(_));
+        // Function arguments and attribute selectors are joined to the preceding token.
+        let join_previous = match &token {
+            TokenTree::Punct(_) => true,
+            TokenTree::Group(group) => group.delimiter() != Delimiter::Brace,
+            TokenTree::Ident(_) | TokenTree::Literal(_) => false,
+        };
+
+        if space_next || (!join_next && !join_previous) {
+            css.push(' ');
+        }
+
+        // A punct is only `Alone` before another punct if there's whitespace between
+        // them.
+        space_next = matches!(&token, TokenTree::Punct(punct) if punct.spacing() == Spacing::Alone)
+            && matches!(tokens.peek(), Some(TokenTree::Punct(_)));
+
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::None => ("", ""),
+                };
+
+                css.push_str(open);
+                css.push_str(&render(group.stream(), index, var_name));
+                css.push_str(close);
+            }
+            TokenTree::Ident(ident) => css.push_str(&ident.to_string()),
+            TokenTree::Punct(punct) => css.push(punct.as_char()),
+            TokenTree::Literal(literal) => css.push_str(&literal.to_string()),
+        }
+
+        join_next = is_punct;
+    }
+
+    css
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+
+    #[test]
+    fn render_selectors() {
+        assert_eq!(
+            render_str("& .child { color: red; }"),
+            "& .child {color:red;}"
+        );
+        assert_eq!(
+            render_str("&.active, &::before { color: red; }"),
+            "&.active, &::before {color:red;}"
+        );
+        assert_eq!(
+            render_str("& > .child { color: red; }"),
+            "& > .child {color:red;}"
+        );
+    }
+
+    #[test]
+    fn render_interpolation() {
+        assert_eq!(
+            render_str("margin: ${top} 0; color: ${color};"),
+            "margin: var(--x-0) 0;color: var(--x-1);"
+        );
+    }
+
+    fn render_str(css: &str) -> String {
+        render(css.parse().unwrap(), &mut 0, |index| format!("--x-{index}"))
+    }
+}
@@ -0,0 +1,245 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use proc_macro_error::{abort, emit_error};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, Expr, ExprMethodCall, FnArg, GenericArgument, Ident, ItemFn, Pat, PatIdent,
+    PathArguments, ReturnType, Stmt, Type, TypeReference,
+};
+
+/// Methods that add children. Once one of these depends on the parameter,
+/// later children have to be added on instantiation too, to keep them in
+/// order.
+const CHILD_METHODS: &[&str] = &[
+    "child",
+    "optional_child",
+    "children",
+    "children_signal",
+    "text",
+];
+
+pub struct Template {
+    item: ItemFn,
+    param: Ident,
+    template_type: Type,
+}
+
+impl Template {
+    pub fn new(item: ItemFn) -> Self {
+        let mut inputs = item.sig.inputs.iter();
+
+        let (param, param_type) = match (inputs.next(), inputs.next()) {
+            (Some(FnArg::Typed(arg)), None) => {
+                let param = match arg.pat.as_ref() {
+                    Pat::Ident(PatIdent {
+                        ident,
+                        by_ref: None,
+                        subpat: None,
+                        ..
+                    }) => ident.clone(),
+                    pat => abort!(pat, "Expected an identifier"),
+                };
+
+                let param_type = match arg.ty.as_ref() {
+                    Type::Reference(TypeReference {
+                        mutability: None,
+                        elem,
+                        ..
+                    }) => elem.as_ref().clone(),
+                    ty => abort!(ty, "Expected a shared reference, like `&Param`"),
+                };
+
+                (param, param_type)
+            }
+            _ => abort!(
+                item.sig.inputs,
+                "Templates must have exactly one parameter, like `param: &Param`"
+            ),
+        };
+
+        let template_type = template_type(&item.sig.output, &param_type);
+
+        Self {
+            item,
+            param,
+            template_type,
+        }
+    }
+
+    pub fn expand(self) -> TokenStream {
+        let Self {
+            item,
+            param,
+            template_type,
+        } = self;
+        let ItemFn {
+            attrs,
+            vis,
+            sig,
+            block,
+        } = item;
+        let mut stmts = block.stmts;
+
+        let root = match stmts.pop() {
+            Some(Stmt::Expr(expr)) => expr,
+            _ => abort!(
+                sig,
+                "Expected the function to end with an element expression"
+            ),
+        };
+
+        for stmt in &stmts {
+            if mentions(stmt, &param) {
+                emit_error!(
+                    stmt,
+                    "Statements are only run when the template is built, so they can't use `{}`",
+                    param
+                );
+            }
+        }
+
+        let (root, _dynamic_children) = transform(root, &param);
+
+        quote!(
+            #(#attrs)*
+            #vis #sig {
+                ::std::thread_local! {
+                    static TEMPLATES: ::std::cell::RefCell<::silkenweb::macros::TemplateCache> =
+                        ::std::default::Default::default();
+                }
+
+                ::silkenweb::macros::TemplateCache::instantiate(
+                    &TEMPLATES,
+                    || -> #template_type {
+                        // The template is only built once, so make sure it can't capture the
+                        // parameter it was first called with.
+                        #[allow(unused_variables)]
+                        let #param = ();
+                        #(#stmts)*
+                        (#root).freeze()
+                    },
+                    |template| template.instantiate(#param),
+                )
+            }
+        )
+    }
+}
+
+/// Turn `Elem<D>` into `Elem<Template<Param, D>, Const>`.
+fn template_type(output: &ReturnType, param_type: &Type) -> Type {
+    let mut ty = match output {
+        ReturnType::Type(_, ty) => ty.as_ref().clone(),
+        ReturnType::Default => abort!(output, "Expected an element return type"),
+    };
+
+    let path = match &mut ty {
+        Type::Path(ty) => &mut ty.path,
+        ty => abort!(ty, "Expected an element type, like `Div<D>`"),
+    };
+    let last = path.segments.last_mut().unwrap();
+
+    let dom_type: Type = match &last.arguments {
+        PathArguments::None => parse_quote!(::silkenweb::dom::DefaultDom),
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(dom_type) => dom_type.clone(),
+            arg => abort!(arg, "Expected a DOM type"),
+        },
+        args => abort!(args, "Expected a single DOM type argument"),
+    };
+
+    last.arguments = PathArguments::AngleBracketed(parse_quote!(<
+        ::silkenweb::dom::Template<#param_type, #dom_type>,
+        ::silkenweb::node::element::Const
+    >));
+
+    ty
+}
+
+/// Move builder methods that depend on `param` into `on_instantiate`.
+///
+/// Returns the new expression, and whether any children are added on
+/// instantiation.
+fn transform(expr: Expr, param: &Ident) -> (TokenStream, bool) {
+    let ExprMethodCall {
+        attrs,
+        receiver,
+        method,
+        turbofish,
+        args,
+        ..
+    } = match expr {
+        Expr::MethodCall(call) => call,
+        expr => {
+            if mentions(&expr, param) {
+                emit_error!(
+                    expr,
+                    "The template's structure can't depend on `{}`. Only use it in builder method arguments.",
+                    param
+                );
+            }
+
+            return (expr.into_token_stream(), false);
+        }
+    };
+
+    let (receiver, dynamic_children) = transform(*receiver, param);
+    let adds_children = CHILD_METHODS.iter().any(|name| method == name);
+    let uses_param = args.iter().any(|arg| mentions(arg, param));
+
+    if uses_param && !dynamic_children && method == "child" && args.len() == 1 {
+        if let Some(child) = args.first().filter(|child| is_element(child, param)) {
+            // Build the child as a template as well.
+            let (child, _dynamic_children) = transform(child.clone(), param);
+
+            return (
+                quote!(#(#attrs)* #receiver.#method #turbofish (#child)),
+                false,
+            );
+        }
+    }
+
+    if uses_param || (adds_children && dynamic_children) {
+        let element = Ident::new("element", Span::mixed_site());
+
+        (
+            quote!(
+                #(#attrs)*
+                #receiver.on_instantiate(move |#element, #param| {
+                    #element.#method #turbofish (#args)
+                })
+            ),
+            dynamic_children || adds_children,
+        )
+    } else {
+        (
+            quote!(#(#attrs)* #receiver.#method #turbofish (#args)),
+            dynamic_children,
+        )
+    }
+}
+
+/// Is `expr` a chain of builder methods on an element that doesn't depend on
+/// `param`, like `div().text(param.text())`?
+fn is_element(expr: &Expr, param: &Ident) -> bool {
+    let mut root = match expr {
+        Expr::MethodCall(call) => call.receiver.as_ref(),
+        _ => return false,
+    };
+
+    while let Expr::MethodCall(call) = root {
+        root = call.receiver.as_ref();
+    }
+
+    matches!(root, Expr::Call(_)) && !mentions(root, param)
+}
+
+fn mentions(tokens: &impl ToTokens, param: &Ident) -> bool {
+    fn tokens_mention(tokens: TokenStream, param: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => &ident == param,
+            TokenTree::Group(group) => tokens_mention(group.stream(), param),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        })
+    }
+
+    tokens_mention(tokens.to_token_stream(), param)
+}
@@ -0,0 +1,291 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse_quote_spanned,
+    spanned::Spanned,
+    token::Brace,
+    Expr, ExprLit, Ident, Lit, LitStr, Path, Token,
+};
+
+pub struct View(Element);
+
+impl Parse for View {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let root = input.parse()?;
+
+        if !input.is_empty() {
+            return Err(input.error("Expected a single root element"));
+        }
+
+        Ok(Self(root))
+    }
+}
+
+impl View {
+    pub fn expand(self) -> TokenStream {
+        let root = self.0.expand();
+
+        quote!({
+            #[allow(unused_imports)]
+            use ::silkenweb::{
+                elements::{
+                    mathml::Global as _,
+                    svg::attributes::{
+                        AnimationTiming as _, AnimationValue as _, ConditionalProcessing as _,
+                        Core as _, FilterPrimitives as _, OtherAnimation as _, Presentation as _,
+                        TransferFunction as _,
+                    },
+                    AriaElement as _, ElementEvents as _, HtmlElement as _,
+                    HtmlElementEvents as _,
+                },
+                node::element::Element as _,
+            };
+
+            #root
+        })
+    }
+}
+
+/// `<name attribute=value ...>children</name>` or `<name attribute=value ...
+/// />`
+struct Element {
+    name: Path,
+    attributes: Vec<Attribute>,
+    children: Vec<Child>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name = input.call(Path::parse_mod_style)?;
+        let mut attributes = Vec::new();
+
+        while !(input.peek(Token![>]) || input.peek(Token![/])) {
+            if input.is_empty() {
+                return Err(input.error("Expected `>` or `/>`"));
+            }
+
+            attributes.push(input.parse()?);
+        }
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+
+            return Ok(Self {
+                name,
+                attributes,
+                children: Vec::new(),
+            });
+        }
+
+        input.parse::<Token![>]>()?;
+        let mut children = Vec::new();
+
+        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+            if input.is_empty() {
+                return Err(input.error(format!("Expected `</{}>`", path_text(&name))));
+            }
+
+            children.push(input.parse()?);
+        }
+
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let closing_name = input.call(Path::parse_mod_style)?;
+
+        if path_text(&closing_name) != path_text(&name) {
+            return Err(syn::Error::new(
+                closing_name.span(),
+                format!("Expected `</{}>`", path_text(&name)),
+            ));
+        }
+
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            name,
+            attributes,
+            children,
+        })
+    }
+}
+
+impl Element {
+    fn expand(self) -> TokenStream {
+        let element = element_var();
+        let name = self.name;
+        let constructor = match name.get_ident() {
+            Some(ident) => quote_spanned!(ident.span()=> ::silkenweb::elements::html::#ident),
+            None => quote!(#name),
+        };
+        let attributes = self.attributes.into_iter().map(|attribute| {
+            let method = attribute.method;
+            let value = attribute.value.map_or_else(
+                || quote_spanned!(method.span()=> true),
+                |value| quote!(#value),
+            );
+
+            quote!(let #element = #element.#method(#value);)
+        });
+        let children = self.children.into_iter().map(|child| match child {
+            Child::Element(child) => {
+                let child = child.expand();
+                quote!(
+                    let #element = ::silkenweb::node::element::ParentElement::child(#element, #child);
+                )
+            }
+            Child::Text(text) => quote!(
+                let #element = ::silkenweb::node::element::ParentElement::text(#element, #text);
+            ),
+            Child::Expr(expr) => quote_spanned!(expr.span()=>
+                let #element = ::silkenweb::macros::ViewChild::add_to(#expr, #element);
+            ),
+        });
+
+        quote!({
+            let #element = #constructor();
+            #(#attributes)*
+            #(#children)*
+            #element
+        })
+    }
+}
+
+/// `name=value`, `name` or `on:event={handler}`.
+///
+/// `name` is the builder method, with `-` allowed in place of `_`. Without a
+/// value, it's set to `true`.
+struct Attribute {
+    method: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (name, span) = dashed_name(input)?;
+
+        let method = if name == "on" && input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            let (event, span) = dashed_name(input)?;
+            format_ident!("on_{}", event, span = span)
+        } else {
+            method_ident(&name, span)
+        };
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+
+            Some(if input.peek(Brace) {
+                braced_expr(input)?
+            } else {
+                Expr::Lit(ExprLit {
+                    attrs: Vec::new(),
+                    lit: input.parse::<Lit>()?,
+                })
+            })
+        } else {
+            None
+        };
+
+        if method == "class" {
+            if let Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(classes),
+                ..
+            })) = &value
+            {
+                if classes.value().contains(char::is_whitespace) {
+                    return class_list(&method, classes);
+                }
+            }
+        }
+
+        Ok(Self { method, value })
+    }
+}
+
+/// Class names can't contain whitespace, so split a literal like
+/// `class="btn primary"` into `classes(["btn", "primary"])`.
+fn class_list(method: &Ident, classes: &LitStr) -> syn::Result<Attribute> {
+    let span = classes.span();
+    let names: Vec<LitStr> = classes
+        .value()
+        .split_whitespace()
+        .map(|name| LitStr::new(name, span))
+        .collect();
+
+    if names.is_empty() {
+        return Err(syn::Error::new(span, "Expected at least one class name"));
+    }
+
+    Ok(Attribute {
+        method: Ident::new("classes", method.span()),
+        value: Some(parse_quote_spanned!(span=> [#(#names),*])),
+    })
+}
+
+enum Child {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            Ok(Self::Element(input.parse()?))
+        } else if input.peek(LitStr) {
+            Ok(Self::Text(input.parse()?))
+        } else if input.peek(Brace) {
+            Ok(Self::Expr(braced_expr(input)?))
+        } else {
+            Err(input.error("Expected an element, string literal or `{ expression }`"))
+        }
+    }
+}
+
+fn braced_expr(input: ParseStream) -> syn::Result<Expr> {
+    let content;
+    braced!(content in input);
+    content.parse()
+}
+
+/// Parse a name like `aria-label`, converting it to `aria_label`.
+fn dashed_name(input: ParseStream) -> syn::Result<(String, Span)> {
+    let first = input.call(Ident::parse_any)?;
+    let span = first.span();
+    let mut name = first.unraw().to_string();
+
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        name.push('_');
+        name.push_str(&input.call(Ident::parse_any)?.unraw().to_string());
+    }
+
+    Ok((name, span))
+}
+
+/// Make an identifier for a builder method, using a raw identifier for
+/// keywords like `type`.
+fn method_ident(name: &str, span: Span) -> Ident {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        Ident::new(name, span)
+    } else {
+        Ident::new_raw(name, span)
+    }
+}
+
+fn path_text(path: &Path) -> String {
+    quote!(#path).to_string()
+}
+
+/// The variable that holds each element as it's built.
+///
+/// This is hygienic, so it doesn't clash with variables in the user's
+/// expressions.
+fn element_var() -> Ident {
+    Ident::new("element", Span::mixed_site())
+}
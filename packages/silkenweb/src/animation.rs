@@ -54,7 +54,11 @@ pub fn finite_animation(duration_millis: f64) -> impl Signal<Item = Option<f64>>
 ///
 /// ```no_run
 /// # use silkenweb::{animation::infinite_animation, prelude::*};
-/// # use svg::{attributes::Presentation, content_type::Length::Px, rect, svg, Svg};
+/// # use svg::{
+/// #     attributes::Presentation,
+/// #     content_type::{Length::Px, Transform},
+/// #     rect, svg, Svg,
+/// # };
 /// # let doc: Svg =
 /// svg().width(200.0).height(200.0).child(
 ///     rect()
@@ -62,9 +66,9 @@ pub fn finite_animation(duration_millis: f64) -> impl Signal<Item = Option<f64>>
 ///         .y(Px(25.0))
 ///         .width(Px(50.0))
 ///         .height(Px(50.0))
-///         .transform(Sig(
-///             infinite_animation().map(|time| format!("rotate({} 50 50)", time / 10.0))
-///         )),
+///         .transform(Sig(infinite_animation().map(|time| {
+///             Transform::new().rotate_about(time / 10.0, 50.0, 50.0)
+///         }))),
 /// );
 /// ```
 ///
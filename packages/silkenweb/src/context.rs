@@ -0,0 +1,144 @@
+//! Typed context, for passing data down the element tree.
+//!
+//! A value is provided for an element and its descendants with
+//! [`Element::provide_context`], and read with [`Element::use_context`].
+//!
+//! [`Element::provide_context`]: crate::node::element::Element::provide_context
+//! [`Element::use_context`]: crate::node::element::Element::use_context
+//!
+//! Context follows the element tree, not where elements are built. An element
+//! sees the context of whatever it's attached to, even if it was built first,
+//! so `use_context` returns a signal that updates as the element, or any of
+//! its ancestors, are attached. This is the same whichever
+//! [`Dom`][crate::dom::Dom] is used.
+//!
+//! # Example
+//!
+//! ```
+//! # use html::{div, p, Div, P};
+//! # use silkenweb::{
+//! #     dom::Dry,
+//! #     prelude::*,
+//! #     task::{render_now, server},
+//! # };
+//! #[derive(Clone)]
+//! struct User(&'static str);
+//!
+//! fn greeting() -> P<Dry> {
+//!     let greeting = p();
+//!     let user = greeting.use_context::<User>();
+//!
+//!     greeting.text(Sig(user.map(|user| {
+//!         format!("Hello, {}!", user.map_or("stranger", |user| user.0))
+//!     })))
+//! }
+//!
+//! # server::block_on(server::scope(async {
+//! let app: Div<Dry> = div().provide_context(User("Rustacean")).child(greeting());
+//! let app = app.freeze();
+//!
+//! render_now().await;
+//! assert_eq!(app.to_string(), "<div><p>Hello, Rustacean!</p></div>");
+//! # }))
+//! ```
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    pin::Pin,
+    rc::Rc,
+    task::{self, Poll},
+};
+
+use futures_signals::signal::{always, Mutable, Signal, SignalExt};
+
+/// A signal of the innermost context value of type `T`.
+///
+/// See [`Element::use_context`].
+///
+/// [`Element::use_context`]: crate::node::element::Element::use_context
+#[must_use = "Signals do nothing unless polled"]
+pub struct ContextSignal<T>(Pin<Box<dyn Signal<Item = Option<T>>>>);
+
+impl<T> Signal for ContextSignal<T> {
+    type Item = Option<T>;
+
+    fn poll_change(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_change(cx)
+    }
+}
+
+/// The context for an element, which is linked to its parent's context when
+/// it's attached.
+///
+/// This is only allocated if the element, or one of its descendants, provides
+/// or uses context, or has reactive children.
+#[derive(Default)]
+pub(crate) struct Context(RefCell<Option<Rc<State>>>);
+
+impl Context {
+    pub fn provide<T: 'static>(&self, value: T) {
+        self.state()
+            .values
+            .lock_mut()
+            .insert(TypeId::of::<T>(), Rc::new(value));
+    }
+
+    pub fn signal<T: Clone + 'static>(&self) -> ContextSignal<T> {
+        ContextSignal(State::signal(self.state()))
+    }
+
+    /// Get a shared reference to this context, so children can be added
+    /// after the element is built.
+    pub fn shared(&self) -> Self {
+        Self(RefCell::new(Some(self.state())))
+    }
+
+    /// Make this context the parent of `child`'s context.
+    pub fn add_child(&self, child: &Self) {
+        if let Some(child) = &*child.0.borrow() {
+            child.parent.set(Some(self.state()));
+        }
+    }
+
+    fn state(&self) -> Rc<State> {
+        self.0.borrow_mut().get_or_insert_with(Rc::default).clone()
+    }
+}
+
+#[derive(Default)]
+struct State {
+    values: Mutable<HashMap<TypeId, Rc<dyn Any>>>,
+    parent: Mutable<Option<Rc<State>>>,
+}
+
+impl State {
+    /// The value of type `T` provided by `state`, or the closest ancestor that
+    /// provides one.
+    fn signal<T: Clone + 'static>(state: Rc<Self>) -> Pin<Box<dyn Signal<Item = Option<T>>>> {
+        let parent = state.parent.clone();
+
+        state
+            .values
+            .signal_ref(|values| {
+                values
+                    .get(&TypeId::of::<T>())
+                    .and_then(|value| value.downcast_ref::<T>())
+                    .cloned()
+            })
+            .switch(move |value| match value {
+                Some(value) => always(Some(value)).boxed_local(),
+                None => parent
+                    .signal_cloned()
+                    .switch(|parent| match parent {
+                        Some(parent) => Self::signal(parent),
+                        None => always(None).boxed_local(),
+                    })
+                    .boxed_local(),
+            })
+            .boxed_local()
+    }
+}
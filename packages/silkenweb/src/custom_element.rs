@@ -0,0 +1,285 @@
+//! Define browser [custom elements] with silkenweb.
+//!
+//! Implement [`CustomElement`] and call [`define`] to register it with
+//! `customElements.define`. Each instance renders into its own shadow root
+//! when it's connected to the document, and the rendered DOM is discarded when
+//! it's disconnected.
+//!
+//! Observed attributes and properties are available as signals through
+//! [`Host`]. Properties are untyped [`JsValue`]s, so any value can be passed
+//! from Javascript.
+//!
+//! This only works in the browser.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use html::{p, span};
+//! # use silkenweb::{
+//! #     custom_element::{self, CustomElement, Host},
+//! #     dom::Wet,
+//! #     prelude::*,
+//! # };
+//! struct Greeting;
+//!
+//! impl CustomElement for Greeting {
+//!     const NAME: &'static str = "my-greeting";
+//!     const OBSERVED_ATTRIBUTES: &'static [&'static str] = &["name"];
+//!
+//!     fn render(host: &Host) -> Node<Wet> {
+//!         let name = host
+//!             .attribute("name")
+//!             .map(|name| name.unwrap_or_else(|| "world".to_string()));
+//!
+//!         p().text("Hello, ").child(span().text(Sig(name))).into()
+//!     }
+//! }
+//!
+//! custom_element::define::<Greeting>();
+//! ```
+//!
+//! `<my-greeting name="Rustacean"></my-greeting>` can then be used from HTML.
+//!
+//! [custom elements]: https://developer.mozilla.org/en-US/docs/Web/Web_Components/Using_custom_elements
+use std::{cell::RefCell, collections::HashMap};
+
+use futures_signals::signal::{Mutable, Signal};
+use wasm_bindgen::{
+    prelude::{wasm_bindgen, Closure},
+    JsCast, JsValue, UnwrapThrowExt,
+};
+
+use crate::{
+    dom::{wet::WetElement, Wet},
+    node::{
+        element::{Const, GenericElement, ShadowRootParent},
+        Node,
+    },
+};
+
+/// A custom element implemented in Rust.
+///
+/// See the [module-level documentation](self) for an example.
+pub trait CustomElement: 'static {
+    /// The tag name. This must contain a `-`.
+    const NAME: &'static str;
+
+    /// Attributes that are available as signals with [`Host::attribute`].
+    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &[];
+
+    /// Properties that are available as signals with [`Host::property`].
+    ///
+    /// A Javascript getter and setter is defined for each property.
+    const PROPERTIES: &'static [&'static str] = &[];
+
+    /// Render the shadow DOM for an instance.
+    ///
+    /// This is called each time the instance is connected to the document.
+    fn render(host: &Host) -> Node<Wet>;
+}
+
+/// Register `T` with the browser's custom element registry.
+///
+/// # Panics
+///
+/// This will panic if an element with the same name has already been defined.
+pub fn define<T: CustomElement>() {
+    let connected = Closure::<dyn FnMut(u32, web_sys::HtmlElement)>::new(connected::<T>);
+    let disconnected = Closure::<dyn FnMut(u32)>::new(disconnected);
+    let attribute_changed =
+        Closure::<dyn FnMut(u32, String, Option<String>)>::new(attribute_changed);
+    let property_changed = Closure::<dyn FnMut(u32, String, JsValue)>::new(property_changed);
+
+    define_custom_element(
+        T::NAME,
+        names(T::OBSERVED_ATTRIBUTES),
+        names(T::PROPERTIES),
+        connected.into_js_value().unchecked_ref(),
+        disconnected.into_js_value().unchecked_ref(),
+        attribute_changed.into_js_value().unchecked_ref(),
+        property_changed.into_js_value().unchecked_ref(),
+    )
+    .unwrap_throw();
+}
+
+/// The host element of a [`CustomElement`] instance.
+pub struct Host {
+    element: web_sys::HtmlElement,
+    attributes: HashMap<&'static str, Mutable<Option<String>>>,
+    properties: HashMap<&'static str, Mutable<JsValue>>,
+}
+
+impl Host {
+    /// The host element.
+    pub fn element(&self) -> &web_sys::HtmlElement {
+        &self.element
+    }
+
+    /// A signal of the value of an observed attribute.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `name` isn't in
+    /// [`CustomElement::OBSERVED_ATTRIBUTES`].
+    pub fn attribute(&self, name: &str) -> impl Signal<Item = Option<String>> + 'static {
+        self.attributes
+            .get(name)
+            .unwrap_or_else(|| panic!("`{name}` is not an observed attribute"))
+            .signal_cloned()
+    }
+
+    /// A signal of the value of a property.
+    ///
+    /// The value is `undefined` until the property is set.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `name` isn't in [`CustomElement::PROPERTIES`].
+    pub fn property(&self, name: &str) -> impl Signal<Item = JsValue> + 'static {
+        self.properties
+            .get(name)
+            .unwrap_or_else(|| panic!("`{name}` is not a property"))
+            .signal_cloned()
+    }
+}
+
+struct Instance {
+    host: Host,
+    shadow_host: GenericElement<Wet, Const>,
+}
+
+fn connected<T: CustomElement>(id: u32, element: web_sys::HtmlElement) {
+    let attributes = T::OBSERVED_ATTRIBUTES
+        .iter()
+        .map(|&name| (name, Mutable::new(element.get_attribute(name))))
+        .collect();
+    // Properties are stored on the element, so they survive being disconnected.
+    let properties = T::PROPERTIES
+        .iter()
+        .map(|&name| {
+            let value = js_sys::Reflect::get(&element, &name.into()).unwrap_throw();
+            (name, Mutable::new(value))
+        })
+        .collect();
+    let host = Host {
+        element,
+        attributes,
+        properties,
+    };
+    let shadow_root = T::render(&host);
+    let shadow_host =
+        GenericElement::from_dom(WetElement::from_element(host.element.clone().into()), 0)
+            .attach_shadow_children([shadow_root])
+            .freeze();
+    shadow_host.mount();
+
+    INSTANCES.with(|instances| {
+        instances
+            .borrow_mut()
+            .insert(id, Instance { host, shadow_host })
+    });
+}
+
+fn disconnected(id: u32) {
+    if let Some(instance) = INSTANCES.with(|instances| instances.borrow_mut().remove(&id)) {
+        instance.shadow_host.unmount();
+
+        if let Some(shadow_root) = instance.host.element.shadow_root() {
+            shadow_root.set_inner_html("");
+        }
+    }
+}
+
+fn attribute_changed(id: u32, name: String, value: Option<String>) {
+    // Attributes are read when the element is connected, so we can ignore
+    // changes before then.
+    INSTANCES.with(|instances| {
+        if let Some(instance) = instances.borrow().get(&id) {
+            if let Some(attribute) = instance.host.attributes.get(name.as_str()) {
+                attribute.set_neq(value);
+            }
+        }
+    });
+}
+
+fn property_changed(id: u32, name: String, value: JsValue) {
+    INSTANCES.with(|instances| {
+        if let Some(instance) = instances.borrow().get(&id) {
+            if let Some(property) = instance.host.properties.get(name.as_str()) {
+                property.set(value);
+            }
+        }
+    });
+}
+
+fn names(names: &[&str]) -> js_sys::Array {
+    names.iter().map(|&name| JsValue::from(name)).collect()
+}
+
+thread_local! {
+    static INSTANCES: RefCell<HashMap<u32, Instance>> = RefCell::new(HashMap::new());
+}
+
+#[wasm_bindgen(inline_js = r#"
+    let nextId = 0;
+
+    export function define_custom_element(
+        name,
+        observedAttributes,
+        properties,
+        connected,
+        disconnected,
+        attributeChanged,
+        propertyChanged
+    ) {
+        class SilkenwebElement extends HTMLElement {
+            static get observedAttributes() {
+                return observedAttributes;
+            }
+
+            constructor() {
+                super();
+                this.__silkenwebId = nextId++;
+                this.__silkenwebProperties = {};
+            }
+
+            connectedCallback() {
+                connected(this.__silkenwebId, this);
+            }
+
+            disconnectedCallback() {
+                disconnected(this.__silkenwebId);
+            }
+
+            attributeChangedCallback(name, _oldValue, newValue) {
+                attributeChanged(this.__silkenwebId, name, newValue);
+            }
+        }
+
+        for (const property of properties) {
+            Object.defineProperty(SilkenwebElement.prototype, property, {
+                get() {
+                    return this.__silkenwebProperties[property];
+                },
+                set(value) {
+                    this.__silkenwebProperties[property] = value;
+                    propertyChanged(this.__silkenwebId, property, value);
+                },
+            });
+        }
+
+        customElements.define(name, SilkenwebElement);
+    }
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn define_custom_element(
+        name: &str,
+        observed_attributes: js_sys::Array,
+        properties: js_sys::Array,
+        connected: &js_sys::Function,
+        disconnected: &js_sys::Function,
+        attribute_changed: &js_sys::Function,
+        property_changed: &js_sys::Function,
+    ) -> Result<(), JsValue>;
+}
@@ -168,12 +168,14 @@ impl Document for Wet {
     fn unmount_all() {
         ELEMENTS.with(|elements| {
             for element in elements.take().into_values() {
-                element.dom_element().remove()
+                element.dom_element().remove();
+                element.unmount();
             }
         });
 
         for element in MOUNTED_IN_WET_HEAD.with(|mounted| mounted.take()) {
-            element.dom_element().remove()
+            element.dom_element().remove();
+            element.unmount();
         }
     }
 
@@ -190,6 +192,7 @@ impl Document for Wet {
         document::head()
             .map(|head| {
                 head.append_with_node_1(&dom_element).unwrap_throw();
+                element.mount();
                 MOUNTED_IN_WET_HEAD.with(|mounted| mounted.borrow_mut().push(element));
             })
             .is_some()
@@ -251,6 +254,8 @@ pub struct MountHandle {
 
 impl MountHandle {
     fn new(mount_point: web_sys::Element, element: GenericElement<Wet, Const>) -> Self {
+        element.mount();
+
         Self {
             id: insert_element(element),
             mount_point,
@@ -264,6 +269,7 @@ impl MountHandle {
                 .dom_element()
                 .replace_with_with_node_1(&self.mount_point)
                 .unwrap_throw();
+            element.unmount();
         }
     }
 }
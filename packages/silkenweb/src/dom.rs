@@ -25,7 +25,7 @@ pub(super) mod private;
 mod dry;
 mod hydro;
 mod template;
-mod wet;
+pub(crate) mod wet;
 
 /// The main DOM abstraction.
 ///
@@ -147,6 +147,10 @@ impl private::InstantiableDom for Wet {
 /// It's likely to get a maximum 10-20% increase, so should only be used in hot
 /// code paths.
 ///
+/// The [`template`][crate::template] macro derives a template from a
+/// component function, so you don't need to write [`on_instantiate`] closures
+/// by hand.
+///
 /// # Example
 ///
 /// Type annotations have been provided for clarity, but the types can be
@@ -167,6 +171,8 @@ impl private::InstantiableDom for Wet {
 /// assert_eq!(hello.freeze().to_string(), "<p>Hello, world!</p>");
 /// assert_eq!(goodbye.freeze().to_string(), "<p>Goodbye!</p>");
 /// ```
+///
+/// [`on_instantiate`]: crate::node::element::GenericElement::on_instantiate
 pub struct Template<Param, D: InstantiableDom = DefaultDom>(PhantomData<(Param, D)>);
 
 impl<Param: 'static, D: InstantiableDom> Dom for Template<Param, D> {}
@@ -13,7 +13,10 @@ use super::{
     wet::{WetElement, WetNode},
     Dry,
 };
-use crate::{hydration::HydrationStats, node::element::Namespace};
+use crate::{
+    hydration::HydrationStats,
+    node::element::{Namespace, ShadowRootOptions},
+};
 
 #[derive(Clone)]
 
@@ -76,6 +79,13 @@ impl private::DomElement for DryElement {
         self.0.borrow_mut().attribute(name, value)
     }
 
+    fn style_property<A>(&mut self, name: &str, value: A)
+    where
+        A: crate::attribute::Attribute,
+    {
+        self.0.borrow_mut().style_property(name, value)
+    }
+
     fn on(
         &mut self,
         name: &'static str,
@@ -85,6 +95,10 @@ impl private::DomElement for DryElement {
         self.0.borrow_mut().on(name, f, events)
     }
 
+    fn select_value(&mut self, value: &str) {
+        self.0.borrow_mut().select_value(value)
+    }
+
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         self.0.borrow().try_dom_element()
     }
@@ -95,10 +109,25 @@ impl private::DomElement for DryElement {
 }
 
 impl private::InstantiableDomElement for DryElement {
+    type ShadowRoot = DryShadowRoot;
+
+    fn shadow_root_parent(&mut self) -> DryShadowRoot {
+        self.0.borrow_mut().shadow_children();
+        DryShadowRoot(self.0.clone())
+    }
+
     fn attach_shadow_children(&mut self, children: impl IntoIterator<Item = Self::Node>) {
         self.0.borrow_mut().attach_shadow_children(children)
     }
 
+    fn shadow_root_options(&mut self, options: ShadowRootOptions) {
+        self.0.borrow_mut().shadow_root_options(options)
+    }
+
+    fn adopt_stylesheet(&mut self, css: &str) {
+        self.0.borrow_mut().adopt_stylesheet(css)
+    }
+
     fn clone_node(&self) -> Self {
         Self::from_shared(self.0.borrow().clone_node())
     }
@@ -110,6 +139,39 @@ impl fmt::Display for DryElement {
     }
 }
 
+#[derive(Clone)]
+pub struct DryShadowRoot(Rc<RefCell<SharedDryElement<DryNode>>>);
+
+impl private::DomParent for DryShadowRoot {
+    type Node = DryNode;
+
+    fn append_child(&mut self, child: &DryNode) {
+        self.0.borrow_mut().shadow_children().append(child)
+    }
+
+    fn insert_child_before(&mut self, index: usize, child: &DryNode, next_child: Option<&DryNode>) {
+        self.0
+            .borrow_mut()
+            .shadow_children()
+            .insert_before(index, child, next_child)
+    }
+
+    fn replace_child(&mut self, index: usize, new_child: &DryNode, old_child: &DryNode) {
+        self.0
+            .borrow_mut()
+            .shadow_children()
+            .replace(index, new_child, old_child)
+    }
+
+    fn remove_child(&mut self, index: usize, child: &DryNode) {
+        self.0.borrow_mut().shadow_children().remove(index, child)
+    }
+
+    fn clear_children(&mut self) {
+        self.0.borrow_mut().shadow_children().clear()
+    }
+}
+
 #[derive(Clone)]
 pub struct DryText(Rc<RefCell<SharedDryText<DryNode>>>);
 
@@ -218,22 +280,103 @@ impl DryChild for DryNode {
             DryNode::Text(text) => text.0.borrow_mut().set_next_sibling(next_sibling),
         }
     }
+
+    fn select_option(&self, value: &str) {
+        match self {
+            DryNode::Element(element) => element.0.borrow_mut().select_option(value),
+            DryNode::Text(_) => (),
+        }
+    }
+
+    fn text_content(&self) -> String {
+        match self {
+            DryNode::Element(element) => element.0.borrow().text_content(),
+            DryNode::Text(text) => text.0.borrow().text().to_owned(),
+        }
+    }
+}
+
+/// The children of an element or a shadow root, keeping their `next_sibling`s
+/// up to date.
+pub struct DryChildren<'a, Node>(&'a mut Vec<Node>);
+
+impl<Node: DryChild> DryChildren<'_, Node> {
+    pub fn append(&mut self, child: &Node) {
+        if let Some(last) = self.0.last_mut() {
+            last.set_next_sibling(Some(child));
+        }
+
+        self.0.push(child.clone());
+    }
+
+    pub fn insert_before(&mut self, index: usize, child: &Node, next_child: Option<&Node>) {
+        if index > 0 {
+            self.0[index - 1].set_next_sibling(Some(child));
+        }
+
+        child.set_next_sibling(next_child);
+
+        self.0.insert(index, child.clone());
+    }
+
+    pub fn replace(&mut self, index: usize, new_child: &Node, old_child: &Node) {
+        old_child.set_next_sibling(None);
+
+        if index > 0 {
+            self.0[index - 1].set_next_sibling(Some(new_child));
+        }
+
+        new_child.set_next_sibling(self.0.get(index + 1));
+
+        self.0[index] = new_child.clone();
+    }
+
+    pub fn remove(&mut self, index: usize, child: &Node) {
+        child.set_next_sibling(None);
+        if index > 0 {
+            self.0[index - 1].set_next_sibling(self.0.get(index + 1));
+        }
+
+        self.0.remove(index);
+    }
+
+    pub fn clear(&mut self) {
+        for child in self.0.iter() {
+            child.set_next_sibling(None);
+        }
+
+        self.0.clear();
+    }
 }
 
 pub trait DryChild: Clone {
     fn clone_node(&self) -> Self;
 
     fn set_next_sibling(&self, next_sibling: Option<&Self>);
+
+    /// Set or remove the `selected` attribute on this node if it's an
+    /// `option`, or on the `option`s in it if it's an `optgroup`.
+    fn select_option(&self, value: &str);
+
+    fn text_content(&self) -> String;
 }
 
 pub struct SharedDryElement<Node> {
     namespace: Namespace,
     tag: String,
     attributes: IndexMap<String, String>,
+    /// Kept separately from `attributes`, as splitting the `style` attribute
+    /// back into properties would break values containing `;` or `:`.
+    style_properties: IndexMap<String, String>,
     children: Vec<Node>,
+    shadow_root: Option<ShadowRootOptions>,
+    adopted_stylesheets: Vec<String>,
     shadow_children: Vec<Node>,
     hydrate_actions: Vec<LazyElementAction>,
     next_sibling: Option<Node>,
+    /// The value of a `select`, so `option`s can be marked as selected as
+    /// they're added.
+    selected_value: Option<String>,
 }
 
 impl<Node: DryChild> SharedDryElement<Node> {
@@ -242,10 +385,14 @@ impl<Node: DryChild> SharedDryElement<Node> {
             namespace,
             tag: tag.to_owned(),
             attributes: IndexMap::new(),
+            style_properties: IndexMap::new(),
             children: Vec::new(),
+            shadow_root: None,
+            adopted_stylesheets: Vec::new(),
             shadow_children: Vec::new(),
             hydrate_actions: Vec::new(),
             next_sibling: None,
+            selected_value: None,
         }
     }
 
@@ -262,62 +409,58 @@ impl<Node: DryChild> SharedDryElement<Node> {
     }
 
     pub fn append_child(&mut self, child: &Node) {
-        if let Some(last) = self.children.last_mut() {
-            last.set_next_sibling(Some(child));
-        }
-
-        self.children.push(child.clone());
+        self.select_new_child(child);
+        DryChildren(&mut self.children).append(child)
     }
 
     pub fn insert_child_before(&mut self, index: usize, child: &Node, next_child: Option<&Node>) {
-        if index > 0 {
-            self.children[index - 1].set_next_sibling(Some(child));
-        }
-
-        child.set_next_sibling(next_child);
-
-        self.children.insert(index, child.clone());
+        self.select_new_child(child);
+        DryChildren(&mut self.children).insert_before(index, child, next_child)
     }
 
     pub fn replace_child(&mut self, index: usize, new_child: &Node, old_child: &Node) {
-        old_child.set_next_sibling(None);
-
-        if index > 0 {
-            self.children[index - 1].set_next_sibling(Some(new_child));
-        }
-
-        new_child.set_next_sibling(self.children.get(index + 1));
-
-        self.children[index] = new_child.clone();
+        self.select_new_child(new_child);
+        DryChildren(&mut self.children).replace(index, new_child, old_child)
     }
 
     pub fn remove_child(&mut self, index: usize, child: &Node) {
-        child.set_next_sibling(None);
-        if index > 0 {
-            self.children[index - 1].set_next_sibling(self.children.get(index + 1));
-        }
-
-        self.children.remove(index);
+        DryChildren(&mut self.children).remove(index, child)
     }
 
     pub fn clear_children(&mut self) {
-        for child in &self.children {
-            child.set_next_sibling(None);
-        }
+        DryChildren(&mut self.children).clear()
+    }
 
-        self.children.clear();
+    /// The shadow root's children, attaching a shadow root with the default
+    /// options if there isn't one yet.
+    pub fn shadow_children(&mut self) -> DryChildren<Node> {
+        self.shadow_root
+            .get_or_insert_with(ShadowRootOptions::default);
+        DryChildren(&mut self.shadow_children)
     }
 
     pub fn attach_shadow_children(&mut self, children: impl IntoIterator<Item = Node>) {
-        for child in children {
-            if let Some(previous_child) = self.shadow_children.last_mut() {
-                previous_child.set_next_sibling(Some(&child));
-            }
+        let mut shadow_children = self.shadow_children();
 
-            self.shadow_children.push(child);
+        for child in children {
+            shadow_children.append(&child);
         }
     }
 
+    pub fn shadow_root_options(&mut self, options: ShadowRootOptions) {
+        debug_assert!(
+            self.shadow_root.is_none() || self.shadow_root == Some(options),
+            "Shadow root options must be set before the shadow root is attached"
+        );
+        self.shadow_root.get_or_insert(options);
+    }
+
+    pub fn adopt_stylesheet(&mut self, css: &str) {
+        self.shadow_root
+            .get_or_insert_with(ShadowRootOptions::default);
+        self.adopted_stylesheets.push(css.to_owned());
+    }
+
     pub fn add_class(&mut self, name: &str) {
         self.attributes
             .entry("class".to_owned())
@@ -358,6 +501,66 @@ impl<Node: DryChild> SharedDryElement<Node> {
         }
     }
 
+    pub fn style_property<A>(&mut self, name: &str, value: A)
+    where
+        A: crate::attribute::Attribute,
+    {
+        if let Some(value) = value.text() {
+            self.style_properties
+                .insert(name.to_owned(), value.into_owned());
+        } else {
+            self.style_properties.shift_remove(name);
+        }
+    }
+
+    pub fn select_value(&mut self, value: &str) {
+        for child in &self.children {
+            child.select_option(value);
+        }
+
+        self.selected_value = Some(value.to_owned());
+    }
+
+    fn select_new_child(&self, child: &Node) {
+        if let Some(value) = &self.selected_value {
+            child.select_option(value);
+        }
+    }
+
+    pub fn select_option(&mut self, value: &str) {
+        match self.tag.as_str() {
+            "option" => {
+                // Like the DOM, use the option's text if it has no `value`.
+                let selected = match self.attributes.get("value") {
+                    Some(option_value) => option_value == value,
+                    None => self.text_content().split_ascii_whitespace().join(" ") == value,
+                };
+
+                self.attribute("selected", selected);
+            }
+            "optgroup" => {
+                for child in &self.children {
+                    child.select_option(value);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn text_content(&self) -> String {
+        self.children.iter().map(Node::text_content).collect()
+    }
+
+    /// The `style` attribute, if any style properties are set.
+    fn style(&self) -> Option<String> {
+        (!self.style_properties.is_empty()).then(|| {
+            self.style_properties
+                .iter()
+                .map(|(name, value)| format!("{name}: {value};"))
+                .join(" ")
+        })
+    }
+
     pub fn on(
         &mut self,
         name: &'static str,
@@ -384,10 +587,14 @@ impl<Node: DryChild> SharedDryElement<Node> {
             namespace: self.namespace,
             tag: self.tag.clone(),
             attributes: self.attributes.clone(),
+            style_properties: self.style_properties.clone(),
             children: Self::clone_children(&self.children),
+            shadow_root: self.shadow_root,
+            adopted_stylesheets: self.adopted_stylesheets.clone(),
             shadow_children: Self::clone_children(&self.shadow_children),
             hydrate_actions: Vec::new(),
             next_sibling: None,
+            selected_value: self.selected_value.clone(),
         }
     }
 
@@ -475,8 +682,18 @@ impl SharedDryElement<HydroNode> {
 
         Self::hydrate_children(dom_elem, self.children, tracker);
 
-        if !self.shadow_children.is_empty() {
+        if let Some(options) = self.shadow_root {
+            // A closed declarative shadow root isn't available from `dom_elem`.
+            // Attaching one with the same options returns it, with its children
+            // removed, so they're recreated as we hydrate.
+            elem.shadow_root_options(options);
             let shadow_root = elem.create_shadow_root();
+            Self::remove_server_stylesheets(&shadow_root, self.adopted_stylesheets.len());
+
+            for css in &self.adopted_stylesheets {
+                elem.adopt_stylesheet(css);
+            }
+
             Self::hydrate_children(&shadow_root, self.shadow_children, tracker);
         }
 
@@ -512,6 +729,19 @@ impl SharedDryElement<HydroNode> {
         Self::remove_children_from(dom_elem, current_child);
     }
 
+    /// Remove up to `count` leading `<style>` elements from `shadow_root`.
+    ///
+    /// These are adopted stylesheets rendered on the server, which are replaced
+    /// with constructable stylesheets.
+    fn remove_server_stylesheets(shadow_root: &web_sys::ShadowRoot, count: usize) {
+        for _ in 0..count {
+            match shadow_root.first_element_child() {
+                Some(style) if style.tag_name().eq_ignore_ascii_case("style") => style.remove(),
+                _ => break,
+            }
+        }
+    }
+
     /// Remove `child` and all siblings after `child`
     fn remove_children_from(parent: &web_sys::Node, mut child: Option<web_sys::Node>) {
         while let Some(node) = child {
@@ -540,9 +770,14 @@ impl SharedDryElement<HydroNode> {
             }
         }
 
-        for (name, value) in &self.attributes {
-            let value = value.as_ref();
+        let style = self.style();
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .chain(style.as_deref().map(|style| ("style", style)));
 
+        for (name, value) in attributes {
             let set_attr = if let Some(existing_value) = dom_attr_map.remove(name) {
                 value != existing_value
             } else {
@@ -567,11 +802,23 @@ impl SharedDryElement<HydroNode> {
 impl<Node: fmt::Display> SharedDryElement<Node> {
     #[cfg(feature = "declarative-shadow-dom")]
     fn write_shadow_dom(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.shadow_children.is_empty() {
-            return Ok(());
+        let options = match self.shadow_root {
+            Some(options) => options,
+            None => return Ok(()),
+        };
+
+        let mode = if options.closed { "closed" } else { "open" };
+        write!(f, r#"<template shadowroot="{mode}""#)?;
+
+        if options.delegates_focus {
+            f.write_str(" shadowrootdelegatesfocus")?;
         }
 
-        f.write_str(r#"<template shadowroot="open">"#)?;
+        f.write_str(">")?;
+
+        for css in &self.adopted_stylesheets {
+            write!(f, "<style>{}</style>", encode_text_minimal(css))?;
+        }
 
         for child in &self.shadow_children {
             child.fmt(f)?;
@@ -596,6 +843,10 @@ impl<Node: fmt::Display> fmt::Display for SharedDryElement<Node> {
             write!(f, " {}=\"{}\"", name, encode_double_quoted_attribute(value))?;
         }
 
+        if let Some(style) = self.style() {
+            write!(f, " style=\"{}\"", encode_double_quoted_attribute(&style))?;
+        }
+
         f.write_str(">")?;
 
         self.write_shadow_dom(f)?;
@@ -623,11 +874,21 @@ impl<Node: Into<WetNode>> From<SharedDryElement<Node>> for WetElement {
             wet.attribute(&name, value);
         }
 
+        for (name, value) in dry.style_properties {
+            wet.style_property(&name, value);
+        }
+
         for child in dry.children {
             wet.append_child(&child.into());
         }
 
-        if !dry.shadow_children.is_empty() {
+        if let Some(options) = dry.shadow_root {
+            wet.shadow_root_options(options);
+
+            for css in &dry.adopted_stylesheets {
+                wet.adopt_stylesheet(css);
+            }
+
             wet.attach_shadow_children(dry.shadow_children.into_iter().map(|child| child.into()));
         }
 
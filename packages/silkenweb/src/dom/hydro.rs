@@ -7,12 +7,15 @@ use std::{
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
 use super::{
-    dry::{DryChild, SharedDryElement, SharedDryText},
-    private::{DomElement, DomText, EventStore, InstantiableDomElement, InstantiableDomNode},
-    wet::{WetElement, WetNode, WetText},
+    dry::{DryChild, DryChildren, SharedDryElement, SharedDryText},
+    private::{self, DomElement, DomText, EventStore, InstantiableDomElement, InstantiableDomNode},
+    wet::{WetElement, WetNode, WetShadowRoot, WetText},
     Hydro,
 };
-use crate::{hydration::HydrationStats, node::element::Namespace};
+use crate::{
+    hydration::HydrationStats,
+    node::element::{Namespace, ShadowRootOptions},
+};
 
 #[derive(Clone)]
 pub struct HydroElement(Rc<RefCell<SharedHydroElement>>);
@@ -71,6 +74,22 @@ impl HydroElement {
         }
     }
 
+    fn select_option(&self, value: &str) {
+        match &mut *self.borrow_mut() {
+            SharedHydroElement::Dry(dry) => dry.select_option(value),
+            SharedHydroElement::Wet(_) => (),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
+    fn text_content(&self) -> String {
+        match &*self.borrow() {
+            SharedHydroElement::Dry(dry) => dry.text_content(),
+            SharedHydroElement::Wet(wet) => wet.dom_element().text_content().unwrap_or_default(),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
     fn hydrate_child(
         self,
         parent: &web_sys::Node,
@@ -194,6 +213,17 @@ impl DomElement for HydroElement {
         }
     }
 
+    fn style_property<A>(&mut self, name: &str, value: A)
+    where
+        A: crate::attribute::Attribute,
+    {
+        match &mut *self.borrow_mut() {
+            SharedHydroElement::Dry(dry) => dry.style_property(name, value),
+            SharedHydroElement::Wet(wet) => wet.style_property(name, value),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
     fn on(
         &mut self,
         name: &'static str,
@@ -207,6 +237,14 @@ impl DomElement for HydroElement {
         }
     }
 
+    fn select_value(&mut self, value: &str) {
+        match &mut *self.borrow_mut() {
+            SharedHydroElement::Dry(dry) => dry.select_value(value),
+            SharedHydroElement::Wet(wet) => wet.select_value(value),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         match &*self.borrow_mut() {
             SharedHydroElement::Dry(dry) => dry.try_dom_element(),
@@ -225,6 +263,14 @@ impl DomElement for HydroElement {
 }
 
 impl InstantiableDomElement for HydroElement {
+    type ShadowRoot = HydroShadowRoot;
+
+    fn shadow_root_parent(&mut self) -> HydroShadowRoot {
+        let mut shadow_root = HydroShadowRoot(self.clone());
+        shadow_root.update(|_| (), |_| ());
+        shadow_root
+    }
+
     fn attach_shadow_children(&mut self, children: impl IntoIterator<Item = Self::Node>) {
         match &mut *self.borrow_mut() {
             SharedHydroElement::Dry(dry) => dry.attach_shadow_children(children),
@@ -235,6 +281,22 @@ impl InstantiableDomElement for HydroElement {
         }
     }
 
+    fn shadow_root_options(&mut self, options: ShadowRootOptions) {
+        match &mut *self.borrow_mut() {
+            SharedHydroElement::Dry(dry) => dry.shadow_root_options(options),
+            SharedHydroElement::Wet(wet) => wet.shadow_root_options(options),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
+    fn adopt_stylesheet(&mut self, css: &str) {
+        match &mut *self.borrow_mut() {
+            SharedHydroElement::Dry(dry) => dry.adopt_stylesheet(css),
+            SharedHydroElement::Wet(wet) => wet.adopt_stylesheet(css),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+
     fn clone_node(&self) -> Self {
         Self::from_shared(match &*self.borrow() {
             SharedHydroElement::Dry(dry) => SharedHydroElement::Dry(Box::new(dry.clone_node())),
@@ -244,6 +306,74 @@ impl InstantiableDomElement for HydroElement {
     }
 }
 
+#[derive(Clone)]
+pub struct HydroShadowRoot(HydroElement);
+
+impl HydroShadowRoot {
+    fn update(
+        &mut self,
+        dry: impl FnOnce(DryChildren<HydroNode>),
+        wet: impl FnOnce(&mut WetShadowRoot),
+    ) {
+        match &mut *self.0.borrow_mut() {
+            SharedHydroElement::Dry(dry_elem) => dry(dry_elem.shadow_children()),
+            SharedHydroElement::Wet(wet_elem) => wet(&mut wet_elem.shadow_root_parent()),
+            SharedHydroElement::Unreachable => unreachable!(),
+        }
+    }
+}
+
+impl private::DomParent for HydroShadowRoot {
+    type Node = HydroNode;
+
+    fn append_child(&mut self, child: &HydroNode) {
+        self.update(
+            |mut children| children.append(child),
+            |wet| private::DomParent::append_child(wet, &child.wet()),
+        )
+    }
+
+    fn insert_child_before(
+        &mut self,
+        index: usize,
+        child: &HydroNode,
+        next_child: Option<&HydroNode>,
+    ) {
+        self.update(
+            |mut children| children.insert_before(index, child, next_child),
+            |wet| {
+                private::DomParent::insert_child_before(
+                    wet,
+                    index,
+                    &child.wet(),
+                    next_child.map(|c| c.wet()).as_ref(),
+                )
+            },
+        )
+    }
+
+    fn replace_child(&mut self, index: usize, new_child: &HydroNode, old_child: &HydroNode) {
+        self.update(
+            |mut children| children.replace(index, new_child, old_child),
+            |wet| private::DomParent::replace_child(wet, index, &new_child.wet(), &old_child.wet()),
+        )
+    }
+
+    fn remove_child(&mut self, index: usize, child: &HydroNode) {
+        self.update(
+            |mut children| children.remove(index, child),
+            |wet| private::DomParent::remove_child(wet, index, &child.wet()),
+        )
+    }
+
+    fn clear_children(&mut self) {
+        self.update(
+            |mut children| children.clear(),
+            private::DomParent::clear_children,
+        )
+    }
+}
+
 impl From<HydroElement> for WetNode {
     fn from(elem: HydroElement) -> Self {
         let wet = match elem.0.replace(SharedHydroElement::Unreachable) {
@@ -437,6 +567,21 @@ impl DryChild for HydroNode {
             Self::Wet(_) => (),
         }
     }
+
+    fn select_option(&self, value: &str) {
+        match self {
+            Self::Element(element) => element.select_option(value),
+            Self::Text(_) | Self::Wet(_) => (),
+        }
+    }
+
+    fn text_content(&self) -> String {
+        match self {
+            Self::Text(text) => text.to_string(),
+            Self::Element(element) => element.text_content(),
+            Self::Wet(node) => node.dom_node().text_content().unwrap_or_default(),
+        }
+    }
 }
 
 impl From<HydroNode> for WetNode {
@@ -2,7 +2,10 @@ use std::fmt::Display;
 
 use wasm_bindgen::JsValue;
 
-use crate::{attribute::Attribute, node::element::Namespace};
+use crate::{
+    attribute::Attribute,
+    node::element::{Namespace, ShadowRootOptions},
+};
 
 pub trait Dom: 'static {
     type Element: DomElement<Node = Self::Node>;
@@ -45,8 +48,18 @@ pub trait DomElement: Display + Into<Self::Node> + Clone + 'static {
     where
         A: Attribute;
 
+    fn style_property<A>(&mut self, name: &str, value: A)
+    where
+        A: Attribute;
+
     fn on(&mut self, name: &'static str, f: impl FnMut(JsValue) + 'static, events: &mut EventStore);
 
+    /// Set the value of a `select` element.
+    ///
+    /// When rendering to a string, this sets the `selected` attribute on the
+    /// matching `option`s, including any that are added later.
+    fn select_value(&mut self, value: &str);
+
     fn dom_element(&self) -> web_sys::Element {
         self.try_dom_element().unwrap()
     }
@@ -56,6 +69,55 @@ pub trait DomElement: Display + Into<Self::Node> + Clone + 'static {
     fn effect(&mut self, f: impl FnOnce(&web_sys::Element) + 'static);
 }
 
+/// Something that can have children. This is an element or a shadow root.
+pub trait DomParent: Clone + 'static {
+    type Node;
+
+    fn append_child(&mut self, child: &Self::Node);
+
+    fn insert_child_before(
+        &mut self,
+        index: usize,
+        child: &Self::Node,
+        next_child: Option<&Self::Node>,
+    );
+
+    fn replace_child(&mut self, index: usize, new_child: &Self::Node, old_child: &Self::Node);
+
+    fn remove_child(&mut self, index: usize, child: &Self::Node);
+
+    fn clear_children(&mut self);
+}
+
+impl<E: DomElement> DomParent for E {
+    type Node = E::Node;
+
+    fn append_child(&mut self, child: &Self::Node) {
+        DomElement::append_child(self, child)
+    }
+
+    fn insert_child_before(
+        &mut self,
+        index: usize,
+        child: &Self::Node,
+        next_child: Option<&Self::Node>,
+    ) {
+        DomElement::insert_child_before(self, index, child, next_child)
+    }
+
+    fn replace_child(&mut self, index: usize, new_child: &Self::Node, old_child: &Self::Node) {
+        DomElement::replace_child(self, index, new_child, old_child)
+    }
+
+    fn remove_child(&mut self, index: usize, child: &Self::Node) {
+        DomElement::remove_child(self, index, child)
+    }
+
+    fn clear_children(&mut self) {
+        DomElement::clear_children(self)
+    }
+}
+
 pub trait DomText: Clone + 'static {
     fn new(text: &str) -> Self;
 
@@ -63,8 +125,18 @@ pub trait DomText: Clone + 'static {
 }
 
 pub trait InstantiableDomElement: DomElement {
+    type ShadowRoot: DomParent<Node = Self::Node>;
+
+    /// The shadow root, attaching one with the default options if there isn't
+    /// one yet.
+    fn shadow_root_parent(&mut self) -> Self::ShadowRoot;
+
     fn attach_shadow_children(&mut self, children: impl IntoIterator<Item = Self::Node>);
 
+    fn shadow_root_options(&mut self, options: ShadowRootOptions);
+
+    fn adopt_stylesheet(&mut self, css: &str);
+
     fn clone_node(&self) -> Self;
 }
 
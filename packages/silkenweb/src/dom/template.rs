@@ -96,6 +96,13 @@ where
         self.element.attribute(name, value)
     }
 
+    fn style_property<A>(&mut self, name: &str, value: A)
+    where
+        A: crate::attribute::Attribute,
+    {
+        self.element.style_property(name, value)
+    }
+
     fn on(
         &mut self,
         name: &'static str,
@@ -105,6 +112,10 @@ where
         self.element.on(name, f, events)
     }
 
+    fn select_value(&mut self, value: &str) {
+        self.element.select_value(value)
+    }
+
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         self.element.try_dom_element()
     }
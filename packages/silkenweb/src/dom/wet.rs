@@ -1,14 +1,17 @@
-use std::fmt;
+use std::{cell::RefCell, collections::HashMap, fmt};
 
 use silkenweb_base::document;
-use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue, UnwrapThrowExt};
 use web_sys::{ShadowRootInit, ShadowRootMode};
 
 use super::{
-    private::{DomElement, DomText, EventStore, InstantiableDomElement, InstantiableDomNode},
+    private::{self, DomElement, DomText, EventStore, InstantiableDomElement, InstantiableDomNode},
     Wet,
 };
-use crate::{node::element::Namespace, task::on_animation_frame};
+use crate::{
+    node::element::{Namespace, ShadowRootOptions},
+    task::on_animation_frame,
+};
 
 #[derive(Clone)]
 pub struct WetElement {
@@ -20,13 +23,81 @@ impl WetElement {
         Self { element }
     }
 
-    pub fn create_shadow_root(&self) -> web_sys::ShadowRoot {
-        self.element.shadow_root().unwrap_or_else(|| {
-            self.element
-                .attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
-                .unwrap_throw()
-        })
+    pub fn create_shadow_root(&mut self) -> web_sys::ShadowRoot {
+        self.attach_shadow_root(ShadowRootOptions::default())
+    }
+
+    /// Attach a shadow root with `options`, unless there's already one.
+    fn attach_shadow_root(&mut self, options: ShadowRootOptions) -> web_sys::ShadowRoot {
+        if let Some(shadow_root) = self.shadow_root() {
+            return shadow_root;
+        }
+
+        let mode = if options.closed {
+            ShadowRootMode::Closed
+        } else {
+            ShadowRootMode::Open
+        };
+        let init = ShadowRootInit::new(mode);
+        // Set these dynamically, as not all `web_sys` versions support them.
+        set_property(&init, "delegatesFocus", options.delegates_focus);
+
+        if options.manual_slot_assignment {
+            set_property(&init, "slotAssignment", "manual");
+        }
+
+        let shadow_root = self.element.attach_shadow(&init).unwrap_throw();
+        SHADOW_ROOTS.with(|roots| roots.set(&self.element, &shadow_root));
+        shadow_root
     }
+
+    /// The shadow root we attached to this element, if any.
+    ///
+    /// Closed shadow roots aren't available from the element, so we keep track
+    /// of them by element. Every `WetElement` for the same DOM element sees
+    /// the same shadow root.
+    fn shadow_root(&self) -> Option<web_sys::ShadowRoot> {
+        self.element
+            .shadow_root()
+            .or_else(|| SHADOW_ROOTS.with(|roots| roots.get(&self.element).dyn_into().ok()))
+    }
+}
+
+fn set_property(object: &JsValue, name: &str, value: impl Into<JsValue>) {
+    js_sys::Reflect::set(object, &name.into(), &value.into()).unwrap_throw();
+}
+
+/// Get a constructable stylesheet for `css`, creating it if we haven't already.
+fn constructable_stylesheet(css: &str) -> CssStyleSheet {
+    STYLESHEETS.with(|stylesheets| {
+        stylesheets
+            .borrow_mut()
+            .entry(css.to_owned())
+            .or_insert_with(|| {
+                let stylesheet = CssStyleSheet::new();
+                stylesheet.replace_sync(css);
+                stylesheet
+            })
+            .clone()
+    })
+}
+
+thread_local! {
+    static STYLESHEETS: RefCell<HashMap<String, CssStyleSheet>> = RefCell::new(HashMap::new());
+    static SHADOW_ROOTS: js_sys::WeakMap = js_sys::WeakMap::new();
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = CSSStyleSheet)]
+    #[derive(Clone)]
+    type CssStyleSheet;
+
+    #[wasm_bindgen(constructor, js_class = "CSSStyleSheet")]
+    fn new() -> CssStyleSheet;
+
+    #[wasm_bindgen(method, js_class = "CSSStyleSheet", js_name = replaceSync)]
+    fn replace_sync(this: &CssStyleSheet, text: &str);
 }
 
 impl fmt::Display for WetElement {
@@ -39,9 +110,7 @@ impl DomElement for WetElement {
     type Node = WetNode;
 
     fn new(ns: Namespace, tag: &str) -> Self {
-        Self {
-            element: ns.create_element(tag),
-        }
+        Self::from_element(ns.create_element(tag))
     }
 
     fn append_child(&mut self, child: &WetNode) {
@@ -93,6 +162,22 @@ impl DomElement for WetElement {
         .unwrap_throw()
     }
 
+    fn style_property<A>(&mut self, name: &str, value: A)
+    where
+        A: crate::attribute::Attribute,
+    {
+        // `style` is defined on `ElementCSSInlineStyle`, so this works for SVG and
+        // MathML elements as well.
+        let style = self.element.unchecked_ref::<web_sys::HtmlElement>().style();
+
+        if let Some(value) = value.text() {
+            style.set_property(name, &value)
+        } else {
+            style.remove_property(name).map(|_| ())
+        }
+        .unwrap_throw()
+    }
+
     fn on(
         &mut self,
         name: &'static str,
@@ -102,6 +187,12 @@ impl DomElement for WetElement {
         events.add_listener(&self.element, name, f);
     }
 
+    fn select_value(&mut self, value: &str) {
+        self.element
+            .unchecked_ref::<web_sys::HtmlSelectElement>()
+            .set_value(value)
+    }
+
     fn try_dom_element(&self) -> Option<web_sys::Element> {
         Some(self.element.clone())
     }
@@ -113,6 +204,12 @@ impl DomElement for WetElement {
 }
 
 impl InstantiableDomElement for WetElement {
+    type ShadowRoot = WetShadowRoot;
+
+    fn shadow_root_parent(&mut self) -> WetShadowRoot {
+        WetShadowRoot(self.create_shadow_root())
+    }
+
     fn attach_shadow_children(&mut self, children: impl IntoIterator<Item = Self::Node>) {
         let shadow_root = self.create_shadow_root();
 
@@ -121,14 +218,62 @@ impl InstantiableDomElement for WetElement {
         }
     }
 
+    fn shadow_root_options(&mut self, options: ShadowRootOptions) {
+        self.attach_shadow_root(options);
+    }
+
+    fn adopt_stylesheet(&mut self, css: &str) {
+        let shadow_root = self.create_shadow_root();
+        let adopted = js_sys::Array::from(
+            &js_sys::Reflect::get(&shadow_root, &"adoptedStyleSheets".into()).unwrap_throw(),
+        );
+        adopted.push(&constructable_stylesheet(css));
+        set_property(&shadow_root, "adoptedStyleSheets", adopted);
+    }
+
     fn clone_node(&self) -> Self {
-        Self {
-            element: self
-                .element
+        Self::from_element(
+            self.element
                 .clone_node_with_deep(true)
                 .unwrap()
                 .unchecked_into(),
-        }
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct WetShadowRoot(web_sys::ShadowRoot);
+
+impl private::DomParent for WetShadowRoot {
+    type Node = WetNode;
+
+    fn append_child(&mut self, child: &WetNode) {
+        self.0.append_child(child.dom_node()).unwrap_throw();
+    }
+
+    fn insert_child_before(
+        &mut self,
+        _index: usize,
+        child: &WetNode,
+        next_child: Option<&WetNode>,
+    ) {
+        self.0
+            .insert_before(child.dom_node(), next_child.map(|c| c.dom_node()))
+            .unwrap_throw();
+    }
+
+    fn replace_child(&mut self, _index: usize, new_child: &WetNode, old_child: &WetNode) {
+        self.0
+            .replace_child(new_child.dom_node(), old_child.dom_node())
+            .unwrap_throw();
+    }
+
+    fn remove_child(&mut self, _index: usize, child: &WetNode) {
+        self.0.remove_child(child.dom_node()).unwrap_throw();
+    }
+
+    fn clear_children(&mut self) {
+        self.0.set_text_content(Some(""))
     }
 }
 
@@ -190,9 +335,7 @@ impl InstantiableDomNode for WetNode {
     type DomType = Wet;
 
     fn into_element(self) -> WetElement {
-        WetElement {
-            element: self.0.unchecked_into(),
-        }
+        WetElement::from_element(self.0.unchecked_into())
     }
 
     fn first_child(&self) -> Self {
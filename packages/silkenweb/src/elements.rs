@@ -34,7 +34,9 @@ use wasm_bindgen::JsCast;
 
 use crate::node::element::Element;
 
+mod bind;
 pub mod html;
+pub mod mathml;
 pub mod svg;
 
 /// Wrap a [`web_sys::CustomEvent`].
@@ -0,0 +1,348 @@
+//! Two-way bindings between form controls and [`Mutable`]s.
+//!
+//! Each binding sets the control from the [`Mutable`], and sets the
+//! [`Mutable`] when the user edits the control. When hydrating, anything the
+//! user typed before hydration finished is kept, and copied to the
+//! [`Mutable`].
+use std::{convert::Infallible, rc::Rc, str::FromStr};
+
+use futures_signals::signal::Mutable;
+use silkenweb_signals_ext::value::Sig;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+use super::html::{Input, Select, Textarea};
+use crate::{
+    dom::Dom,
+    node::element::{Element, ParentElement},
+};
+
+impl<D: Dom> Input<D> {
+    /// Bind the `value` of the input to a [`Mutable`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{input, Input};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let name = Mutable::new("Alice".to_string());
+    /// let app: Input<Dry> = input().bind_value(&name);
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<input value="Alice">"#);
+    /// # }))
+    /// ```
+    pub fn bind_value(self, value: &Mutable<String>) -> Self {
+        self.value(Sig(value.signal_cloned()))
+            .bind_text(value, parse_string, |_| ())
+    }
+
+    /// Bind the `checked` state of a checkbox or radio button to a
+    /// [`Mutable`].
+    ///
+    /// Checking a radio button unchecks the others in its group, so their
+    /// bound [`Mutable`]s are set to `false`. This happens whether the user or
+    /// a [`Mutable`] checked it.
+    pub fn bind_checked(self, checked: &Mutable<bool>) -> Self {
+        let on_change = {
+            let checked = checked.clone();
+            move |input: &web_sys::HtmlInputElement| checked.set_neq(input.checked())
+        };
+
+        self.checked(Sig(checked.signal()))
+            .effect({
+                let on_change = on_change.clone();
+                move |input| {
+                    if input.checked() != input.default_checked() {
+                        on_change(input);
+                    }
+                }
+            })
+            .effect_signal(checked.signal(), |input, checked| {
+                if input.checked() != checked {
+                    input.set_checked(checked);
+
+                    if checked {
+                        uncheck_radio_group(input);
+                    }
+                }
+            })
+            .on("change", {
+                let on_change = on_change.clone();
+                move |event| {
+                    let input = current_target(event);
+                    on_change(&input);
+                    uncheck_radio_group(&input);
+                }
+            })
+            .on(RADIO_UNCHECKED, move |event| {
+                on_change(&current_target(event))
+            })
+    }
+
+    /// Bind the `value` of the input to a [`Mutable`], parsing it with
+    /// [`FromStr`].
+    ///
+    /// This is useful for numeric inputs. When the value doesn't parse,
+    /// `value` is left unchanged and the error is put in `error`. `error` is
+    /// set to `None` when the value parses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::num::ParseIntError;
+    /// # use html::{input, Input};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let age = Mutable::new(42u32);
+    /// let age_error: Mutable<Option<ParseIntError>> = Mutable::new(None);
+    /// let app: Input<Dry> = input().r#type("number").bind_parsed(&age, &age_error);
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<input type="number" value="42">"#);
+    /// # }))
+    /// ```
+    pub fn bind_parsed<T>(self, value: &Mutable<T>, error: &Mutable<Option<T::Err>>) -> Self
+    where
+        T: FromStr + ToString + Clone + PartialEq + 'static,
+        T::Err: 'static,
+    {
+        let error = error.clone();
+
+        self.value(Sig(value.signal_ref(T::to_string))).bind_text(
+            value,
+            |text| text.trim().parse(),
+            move |e| error.set(e),
+        )
+    }
+}
+
+impl<D: Dom> Textarea<D> {
+    /// Bind the `value` of the textarea to a [`Mutable`].
+    ///
+    /// The value is rendered as the textarea's text, so don't add any other
+    /// children.
+    pub fn bind_value(self, value: &Mutable<String>) -> Self {
+        self.text(Sig(value.signal_cloned()))
+            .bind_text(value, parse_string, |_| ())
+    }
+}
+
+impl<D: Dom> Select<D> {
+    /// Bind the `value` of the select to a [`Mutable`].
+    ///
+    /// The option with a matching value is selected. When rendering to a
+    /// string, it's marked with the `selected` attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use html::{option, select, Select};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let fruit = Mutable::new("pear".to_string());
+    /// let app: Select<Dry> = select()
+    ///     .bind_value(&fruit)
+    ///     .child(option().value("apple").text("Apple"))
+    ///     .child(option().value("pear").text("Pear"));
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(
+    ///     app.to_string(),
+    ///     r#"<select><option value="apple">Apple</option><option value="pear" selected="">Pear</option></select>"#
+    /// );
+    /// # }))
+    /// ```
+    pub fn bind_value(self, value: &Mutable<String>) -> Self {
+        self.select_value(value.signal_cloned())
+            .bind_text(value, parse_string, |_| ())
+    }
+
+    /// Bind the selected option to a [`Mutable`].
+    ///
+    /// Each option's value should be the [`ToString`] representation of an
+    /// item. Selecting an option that doesn't parse leaves `selected`
+    /// unchanged.
+    pub fn bind_selected<T>(self, selected: &Mutable<T>) -> Self
+    where
+        T: FromStr + ToString + Clone + PartialEq + 'static,
+    {
+        self.select_value(selected.signal_ref(T::to_string))
+            .bind_text(selected, |text| text.parse(), |_| ())
+    }
+}
+
+/// A form control with a text `value`.
+trait FormControl: JsCast + Clone + 'static {
+    fn value(&self) -> String;
+
+    fn set_value(&self, value: &str);
+
+    /// Has the user changed the value since it was rendered?
+    fn is_dirty(&self) -> bool;
+}
+
+impl FormControl for web_sys::HtmlInputElement {
+    fn value(&self) -> String {
+        self.value()
+    }
+
+    fn set_value(&self, value: &str) {
+        self.set_value(value)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.value() != self.default_value()
+    }
+}
+
+impl FormControl for web_sys::HtmlTextAreaElement {
+    fn value(&self) -> String {
+        self.value()
+    }
+
+    fn set_value(&self, value: &str) {
+        self.set_value(value)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.value() != self.default_value()
+    }
+}
+
+impl FormControl for web_sys::HtmlSelectElement {
+    fn value(&self) -> String {
+        self.value()
+    }
+
+    fn set_value(&self, value: &str) {
+        self.set_value(value)
+    }
+
+    fn is_dirty(&self) -> bool {
+        (0..self.length())
+            .filter_map(|index| self.item(index))
+            .filter_map(|option| option.dyn_into::<web_sys::HtmlOptionElement>().ok())
+            .any(|option| option.selected() != option.default_selected())
+    }
+}
+
+trait BindText: Element {
+    /// Bind the text `value` of a form control to `value`.
+    ///
+    /// Errors from `parse` are passed to `on_error`, which is passed `None`
+    /// when `parse` succeeds.
+    fn bind_text<T, E>(
+        self,
+        value: &Mutable<T>,
+        parse: impl Fn(&str) -> Result<T, E> + Clone + 'static,
+        on_error: impl Fn(Option<E>) + 'static,
+    ) -> Self
+    where
+        T: ToString + Clone + PartialEq + 'static,
+        E: 'static;
+}
+
+impl<Elem> BindText for Elem
+where
+    Elem: Element,
+    Elem::DomType: FormControl,
+{
+    fn bind_text<T, E>(
+        self,
+        value: &Mutable<T>,
+        parse: impl Fn(&str) -> Result<T, E> + Clone + 'static,
+        on_error: impl Fn(Option<E>) + 'static,
+    ) -> Self
+    where
+        T: ToString + Clone + PartialEq + 'static,
+        E: 'static,
+    {
+        let on_input = {
+            let value = value.clone();
+            let parse = parse.clone();
+            Rc::new(
+                move |control: &Elem::DomType| match parse(&control.value()) {
+                    Ok(new_value) => {
+                        value.set_neq(new_value);
+                        on_error(None);
+                    }
+                    Err(e) => on_error(Some(e)),
+                },
+            )
+        };
+
+        self.effect({
+            let on_input = on_input.clone();
+            move |control| {
+                // Keep anything the user typed before hydration.
+                if control.is_dirty() {
+                    on_input(control);
+                }
+            }
+        })
+        .effect_signal(value.signal_cloned(), move |control, new_value| {
+            // Don't overwrite equivalent text, like `1.0` for `1`, or the user
+            // would lose their cursor position while typing.
+            if parse(&control.value()).ok().as_ref() != Some(&new_value) {
+                control.set_value(&new_value.to_string());
+            }
+        })
+        .on("input", move |event| on_input(&current_target(event)))
+    }
+}
+
+/// Radio buttons don't get an event when they're unchecked by checking another
+/// button in their group, so bound buttons are sent this instead.
+const RADIO_UNCHECKED: &str = "silkenweb-radio-unchecked";
+
+/// Send [`RADIO_UNCHECKED`] to the other buttons in `radio`'s group.
+///
+/// The group is the radio buttons with the same name and form, in the same
+/// document or shadow root.
+fn uncheck_radio_group(radio: &web_sys::HtmlInputElement) {
+    let name = radio.name();
+
+    if radio.type_() != "radio" || name.is_empty() {
+        return;
+    }
+
+    let root = radio.get_root_node();
+    let selector = "input[type=radio]";
+    let radios = if let Some(root) = root.dyn_ref::<web_sys::Document>() {
+        root.query_selector_all(selector)
+    } else if let Some(root) = root.dyn_ref::<web_sys::DocumentFragment>() {
+        root.query_selector_all(selector)
+    } else if let Some(root) = root.dyn_ref::<web_sys::Element>() {
+        root.query_selector_all(selector)
+    } else {
+        return;
+    };
+    let radios = radios.unwrap_throw();
+    let form = radio.form();
+
+    for index in 0..radios.length() {
+        let other: web_sys::HtmlInputElement = radios.item(index).unwrap_throw().unchecked_into();
+
+        if &other != radio && other.name() == name && other.form() == form {
+            other
+                .dispatch_event(&web_sys::Event::new(RADIO_UNCHECKED).unwrap_throw())
+                .unwrap_throw();
+        }
+    }
+}
+
+fn parse_string(text: &str) -> Result<String, Infallible> {
+    Ok(text.to_string())
+}
+
+fn current_target<T: JsCast>(event: JsValue) -> T {
+    event
+        .unchecked_into::<web_sys::Event>()
+        .current_target()
+        .unwrap_throw()
+        .unchecked_into()
+}
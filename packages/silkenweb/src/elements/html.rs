@@ -6,6 +6,10 @@
 //
 // [Moxie DOM]: https://github.com/anp/moxie
 
+use futures_signals::signal::Signal;
+
+use crate::dom::Dom;
+
 html_element!(
     /// The [HTML `<a>` element (or *anchor* element)][mdn], along with its href
     /// attribute, creates a hyperlink to other web pages, files, locations
@@ -3844,6 +3848,12 @@ html_element!(
 
 parent_element!(select);
 
+impl<D: Dom> Select<D> {
+    pub(crate) fn select_value(self, value: impl Signal<Item = String> + 'static) -> Self {
+        Self(self.0.select_value(value))
+    }
+}
+
 html_element!(
     /// The [HTML `<textarea>` element][mdn] represents a multi-line plain-text
     /// editing control, useful when you want to allow users to enter a
@@ -0,0 +1,585 @@
+//! MathML Elements
+//!
+//! Elements are created in the MathML namespace, so formulas can be rendered
+//! with any [`Dom`][crate::dom::Dom].
+//!
+//! # Example
+//!
+//! ```
+//! # use silkenweb::{dom::Dry, elements::mathml::*, prelude::*};
+//! // x²
+//! let formula: Math<Dry> = math().child(msup().child(mi().text("x")).child(mn().text("2")));
+//!
+//! assert_eq!(
+//!     formula.freeze().to_string(),
+//!     "<math><msup><mi>x</mi><mn>2</mn></msup></math>"
+//! );
+//! ```
+use std::borrow::Cow;
+
+use silkenweb_signals_ext::value::Value;
+
+use crate::{
+    attribute::{AsAttribute, Attribute},
+    dom::Dom,
+    node::element::Element,
+};
+
+macro_rules! mathml_global_attributes {
+    ($($t:tt)*) => {
+        attributes![
+            [
+                attribute_parent = (),
+                attribute_doc_macro = mathml_global_attribute_doc
+            ]
+
+            $($t)*
+        ];
+    };
+}
+
+/// Define setters for MathML boolean attributes, which take a `bool` and are
+/// set to [`Boolean`] values.
+macro_rules! boolean_attributes {
+    (
+        [
+            $(visibility = $visibility:vis,)?
+            attribute_parent = $element:expr,
+            attribute_doc_macro = $attr_doc_macro:ident
+        ]
+        $(
+            $(#[$attr_meta:meta])*
+            $attr:ident
+        ),* $(,)?
+    ) => {
+        $(
+            #[doc = $attr_doc_macro!($element, stringify!($attr))]
+            #[doc = ""]
+            $(#[$attr_meta])*
+            $($visibility)? fn $attr<'a>(
+                self,
+                value: impl $crate::value::RefSignalOrValue<'a, Item = bool>,
+            ) -> Self {
+                $crate::node::element::Element::attribute(self, stringify!($attr), value.map(Boolean))
+            }
+        )*
+    };
+}
+
+macro_rules! mathml_global_attribute_doc {
+    ($element:expr, $name:expr) => {
+        concat!(
+            "The MathML Global [`",
+            $name,
+            "`](https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes/",
+            $name,
+            ") attribute"
+        )
+    };
+}
+
+/// MathML [global] attributes
+///
+/// [global]: https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes
+pub trait Global: Element {
+    mathml_global_attributes![
+        /// The directionality of the formula. Either `ltr` or `rtl`.
+        dir: String,
+        /// Defines a unique identifier (ID) which must be unique in the whole
+        /// document.
+        id: String,
+        /// The background color of the element, as a CSS color.
+        mathbackground: String,
+        /// The color of the element, as a CSS color.
+        mathcolor: String,
+        /// The font size of the element, as a CSS length.
+        mathsize: String,
+        /// A cryptographic nonce ("number used once") which can be used by
+        /// Content Security Policy to determine whether or not a given fetch
+        /// will be allowed to proceed.
+        nonce: String,
+        /// The math depth of the element. An integer sets the depth, and `+n`
+        /// or `-n` is relative to the parent's depth.
+        scriptlevel: String,
+        /// Specifies style information for the element.
+        style: String,
+        /// Allows you to control whether an element is focusable and to define
+        /// the relative order of the element for the purposes of sequential
+        /// focus navigation.
+        tabindex: i32,
+    ];
+
+    boolean_attributes![
+        [
+            attribute_parent = (),
+            attribute_doc_macro = mathml_global_attribute_doc
+        ]
+
+        /// Whether the element is rendered in display style, rather than
+        /// compact inline style.
+        displaystyle,
+    ];
+}
+
+/// A MathML boolean attribute value.
+///
+/// Unlike HTML boolean attributes, which are set or unset, MathML boolean
+/// attributes are set to `true` or `false`. When they're unset, the default
+/// depends on the element.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Boolean(pub bool);
+
+impl Attribute for Boolean {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(if self.0 { "true" } else { "false" }))
+    }
+}
+
+impl AsAttribute<Boolean> for Boolean {}
+
+impl Value for Boolean {}
+
+mathml_element!(
+    /// The top-level element in MathML. Every valid MathML instance must be
+    /// wrapped in it.
+    math = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// How the formula is rendered. Either `block`, for a separate
+            /// block, or `inline`, for within text.
+            display: String,
+        };
+    }
+);
+
+parent_element!(math);
+
+mathml_element!(
+    /// Contains an annotation of a formula in a non-XML format, such as
+    /// TeX.
+    annotation = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The encoding of the annotation, for example `application/x-tex`.
+            encoding: String,
+        };
+    }
+);
+
+parent_element!(annotation);
+
+mathml_element!(
+    /// Contains an annotation of a formula in an XML format, such as
+    /// content MathML or SVG.
+    annotation_xml = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The encoding of the annotation, for example
+            /// `application/mathml-content+xml`.
+            encoding: String,
+        };
+    }
+);
+
+parent_element!(annotation_xml);
+
+mathml_element!(
+    /// Displays its content as an error message.
+    merror = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(merror);
+
+mathml_element!(
+    /// Displays a fraction, with a numerator and denominator.
+    mfrac = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The thickness of the fraction line, as a CSS length.
+            linethickness: String,
+        };
+    }
+);
+
+parent_element!(mfrac);
+
+mathml_element!(
+    /// An identifier, such as a function name, variable or symbolic
+    /// constant.
+    mi = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The logical class of the identifier. Only `normal` is supported
+            /// by MathML Core, which disables automatic italics for single
+            /// characters.
+            mathvariant: String,
+        };
+    }
+);
+
+parent_element!(mi);
+
+mathml_element!(
+    /// Attaches prescripts and tensor indices to a base expression.
+    mmultiscripts = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mmultiscripts);
+
+mathml_element!(
+    /// A numeric literal.
+    mn = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mn);
+
+mathml_element!(
+    /// An operator, such as a parenthesis, separator or mathematical
+    /// operator.
+    mo = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The role of the operator in the enclosing expression. One of
+            /// `prefix`, `infix` or `postfix`.
+            form: String,
+            /// The amount of space before the operator, as a CSS length.
+            lspace: String,
+            /// The maximum size of a stretchy operator, as a CSS length.
+            maxsize: String,
+            /// The minimum size of a stretchy operator, as a CSS length.
+            minsize: String,
+            /// The amount of space after the operator, as a CSS length.
+            rspace: String,
+        };
+    }
+);
+
+parent_element!(mo);
+
+impl<D: Dom> Mo<D> {
+    boolean_attributes![
+        [
+            visibility = pub,
+            attribute_parent = "mo",
+            attribute_doc_macro = mathml_attribute_doc
+        ]
+
+        /// Whether the operator is an accent when used as an underscript or
+        /// overscript.
+        accent,
+        /// Whether the operator is a fence, such as a parenthesis.
+        fence,
+        /// Whether the operator is drawn larger in display style, like `∑`.
+        largeop,
+        /// Whether attached underscripts and overscripts move to subscript
+        /// and superscript positions in inline style.
+        movablelimits,
+        /// Whether the operator is a separator, such as a comma.
+        separator,
+        /// Whether the operator stretches to the size of the adjacent
+        /// elements.
+        stretchy,
+        /// Whether a stretchy operator is kept symmetric about the math axis.
+        symmetric,
+    ];
+}
+
+mathml_element!(
+    /// Attaches an accent or a limit over an expression.
+    mover = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mover);
+
+impl<D: Dom> Mover<D> {
+    boolean_attributes![
+        [
+            visibility = pub,
+            attribute_parent = "mover",
+            attribute_doc_macro = mathml_attribute_doc
+        ]
+
+        /// Whether the overscript is an accent, which is drawn closer to the
+        /// base expression.
+        accent,
+    ];
+}
+
+mathml_element!(
+    /// Adds extra padding and changes the positioning of its content.
+    mpadded = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The desired depth (below the baseline), as a CSS length.
+            depth: String,
+            /// The desired height (above the baseline), as a CSS length.
+            height: String,
+            /// The horizontal offset of the content, as a CSS length.
+            lspace: String,
+            /// The vertical offset of the content, as a CSS length.
+            voffset: String,
+            /// The desired width, as a CSS length.
+            width: String,
+        };
+    }
+);
+
+parent_element!(mpadded);
+
+mathml_element!(
+    /// Renders its content invisibly, while keeping its dimensions.
+    mphantom = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mphantom);
+
+mathml_element!(
+    /// Separates postscripts from prescripts within `<mmultiscripts>`.
+    mprescripts = {
+        dom_type: web_sys::Element;
+    }
+);
+
+mathml_element!(
+    /// Displays a root with an explicit index.
+    mroot = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mroot);
+
+mathml_element!(
+    /// Groups sub-expressions, which usually contain one or more operators
+    /// with their respective operands.
+    mrow = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mrow);
+
+mathml_element!(
+    /// A string literal, for programming languages and computer algebra
+    /// systems.
+    ms = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(ms);
+
+mathml_element!(
+    /// Displays a blank space, whose size is set by its attributes.
+    mspace = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The desired depth (below the baseline), as a CSS length.
+            depth: String,
+            /// The desired height (above the baseline), as a CSS length.
+            height: String,
+            /// The desired width, as a CSS length.
+            width: String,
+        };
+    }
+);
+
+mathml_element!(
+    /// Displays a square root, without an explicit index.
+    msqrt = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(msqrt);
+
+mathml_element!(
+    /// Changes the style of its children.
+    mstyle = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mstyle);
+
+mathml_element!(
+    /// Attaches a subscript to an expression.
+    msub = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(msub);
+
+mathml_element!(
+    /// Attaches a subscript and a superscript to an expression.
+    msubsup = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(msubsup);
+
+mathml_element!(
+    /// Attaches a superscript to an expression.
+    msup = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(msup);
+
+mathml_element!(
+    /// Creates tables or matrices. It contains `<mtr>` rows.
+    mtable = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The vertical alignment of the table with respect to its
+            /// environment.
+            align: String,
+            /// The horizontal alignment of the cells, as a space separated list
+            /// of `left`, `center` or `right` for each column.
+            columnalign: String,
+            /// The column borders, as a space separated list of `none`, `solid`
+            /// or `dashed`.
+            columnlines: String,
+            /// The space between columns, as a space separated list of CSS
+            /// lengths.
+            columnspacing: String,
+            /// The borders of the table. One of `none`, `solid` or `dashed`.
+            frame: String,
+            /// The space between the table and the frame, as two CSS lengths.
+            framespacing: String,
+            /// The vertical alignment of the cells, as a space separated list of
+            /// `axis`, `baseline`, `bottom`, `center` or `top` for each row.
+            rowalign: String,
+            /// The row borders, as a space separated list of `none`, `solid` or
+            /// `dashed`.
+            rowlines: String,
+            /// The space between rows, as a space separated list of CSS
+            /// lengths.
+            rowspacing: String,
+            /// The width of the table, as a CSS length.
+            width: String,
+        };
+    }
+);
+
+parent_element!(mtable);
+
+mathml_element!(
+    /// A cell in a table or a matrix.
+    mtd = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The horizontal alignment of the cell. One of `left`, `center` or
+            /// `right`.
+            columnalign: String,
+            /// The number of columns the cell extends.
+            columnspan: u32,
+            /// The vertical alignment of the cell. One of `axis`, `baseline`,
+            /// `bottom`, `center` or `top`.
+            rowalign: String,
+            /// The number of rows the cell extends.
+            rowspan: u32,
+        };
+    }
+);
+
+parent_element!(mtd);
+
+mathml_element!(
+    /// Text, which is displayed without any special meaning.
+    mtext = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(mtext);
+
+mathml_element!(
+    /// A row in a table or a matrix. It contains `<mtd>` cells.
+    mtr = {
+        dom_type: web_sys::Element;
+        attributes {
+            /// The horizontal alignment of the cells in the row, as a space
+            /// separated list of `left`, `center` or `right` for each cell.
+            columnalign: String,
+            /// The vertical alignment of the cells in the row. One of `axis`,
+            /// `baseline`, `bottom`, `center` or `top`.
+            rowalign: String,
+        };
+    }
+);
+
+parent_element!(mtr);
+
+mathml_element!(
+    /// Attaches an accent or a limit under an expression.
+    munder = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(munder);
+
+impl<D: Dom> Munder<D> {
+    boolean_attributes![
+        [
+            visibility = pub,
+            attribute_parent = "munder",
+            attribute_doc_macro = mathml_attribute_doc
+        ]
+
+        /// Whether the underscript is an accent, which is drawn closer to the
+        /// base expression.
+        accentunder,
+    ];
+}
+
+mathml_element!(
+    /// Attaches accents or limits both under and over an expression.
+    munderover = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(munderover);
+
+impl<D: Dom> Munderover<D> {
+    boolean_attributes![
+        [
+            visibility = pub,
+            attribute_parent = "munderover",
+            attribute_doc_macro = mathml_attribute_doc
+        ]
+
+        /// Whether the overscript is an accent, which is drawn closer to the
+        /// base expression.
+        accent,
+        /// Whether the underscript is an accent, which is drawn closer to the
+        /// base expression.
+        accentunder,
+    ];
+}
+
+mathml_element!(
+    /// Associates annotations with a formula. The first child is the
+    /// formula, and the others are `<annotation>` or `<annotation-xml>`
+    /// elements.
+    semantics = {
+        dom_type: web_sys::Element;
+    }
+);
+
+parent_element!(semantics);
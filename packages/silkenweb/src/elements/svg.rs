@@ -5,7 +5,7 @@ use self::{
         AnimationTiming, AnimationValue, ConditionalProcessing, FilterPrimitives, OtherAnimation,
         Presentation, TransferFunction,
     },
-    content_type::{AutoOrLength, Length},
+    content_type::{AutoOrLength, Length, Points, Transform, ViewBox},
 };
 use crate::{dom::Dom, elements::svg::content_type::NumberOrPercentage};
 
@@ -698,6 +698,66 @@ parent_element!(line);
 impl<D: Dom> ConditionalProcessing for Line<D> {}
 impl<D: Dom> Presentation for Line<D> {}
 
+svg_element!(
+    /// The `<linearGradient>` element lets authors define linear gradients to
+    /// apply to other SVG elements. Reference it with
+    /// [`Paint::Url`][content_type::Paint::Url].
+    linear_gradient("linearGradient") = {
+        dom_type: web_sys::SvgLinearGradientElement;
+
+        attributes {
+            /// This attribute defines the coordinate system for attributes
+            /// x1, x2, y1, y2. Value type: userSpaceOnUse|objectBoundingBox;
+            /// Default value: objectBoundingBox; Animatable: yes
+            gradient_units("gradientUnits"): String,
+
+            /// This attribute provides additional transformation to the
+            /// gradient coordinate system. Value type: `<transform-list>`;
+            /// Default value: identity transform; Animatable: yes
+            gradient_transform("gradientTransform"): Transform,
+
+            /// This attribute defines a reference to another gradient that will
+            /// be used as a template. Value type: `<URL>`; Default value: none;
+            /// Animatable: yes
+            href: String,
+
+            /// This attribute indicates how the gradient behaves if it starts
+            /// or ends inside the bounds of the shape containing the gradient.
+            /// Value type: pad|reflect|repeat; Default value: pad; Animatable:
+            /// yes
+            spread_method("spreadMethod"): String,
+
+            /// This attribute defines the x coordinate of the starting point of
+            /// the vector gradient along which the linear gradient is drawn.
+            /// Value type: `<length>`|`<percentage>`; Default value: 0%;
+            /// Animatable: yes
+            x1: Length,
+
+            /// This attribute defines the x coordinate of the ending point of
+            /// the vector gradient along which the linear gradient is drawn.
+            /// Value type: `<length>`|`<percentage>`; Default value: 100%;
+            /// Animatable: yes
+            x2: Length,
+
+            /// This attribute defines the y coordinate of the starting point of
+            /// the vector gradient along which the linear gradient is drawn.
+            /// Value type: `<length>`|`<percentage>`; Default value: 0%;
+            /// Animatable: yes
+            y1: Length,
+
+            /// This attribute defines the y coordinate of the ending point of
+            /// the vector gradient along which the linear gradient is drawn.
+            /// Value type: `<length>`|`<percentage>`; Default value: 0%;
+            /// Animatable: yes
+            y2: Length,
+        };
+    }
+);
+
+parent_element!(linear_gradient);
+
+impl<D: Dom> Presentation for LinearGradient<D> {}
+
 svg_element!(
     marker = {
         dom_type: web_sys::SvgMarkerElement;
@@ -743,7 +803,7 @@ svg_element!(
             /// This attribute defines the bound of the SVG viewport for the
             /// current SVG fragment. Value type: `<list-of-numbers>` ; Default
             /// value: none; Animatable: yes
-            view_box("viewBox"): String,
+            view_box("viewBox"): ViewBox,
         };
     }
 );
@@ -858,7 +918,7 @@ svg_element!(
             /// transformation from the pattern coordinate system onto the
             /// target coordinate system. Value type: `<transform-list>`; Default
             /// value: none; Animatable: yes
-            pattern_transform("patternTransform"): String,
+            pattern_transform("patternTransform"): Transform,
 
             /// This attribute defines the coordinate system for attributes x,
             /// y, width, and height. Value type:
@@ -876,7 +936,7 @@ svg_element!(
             /// This attribute defines the bound of the SVG viewport for the
             /// pattern fragment. Value type: `<list-of-numbers>` ; Default value:
             /// none; Animatable: yes
-            view_box("viewBox"): String,
+            view_box("viewBox"): ViewBox,
 
             /// This attribute determines the width of the pattern tile. Value
             /// type: `<length>`|`<percentage>` ; Default value: 0; Animatable: yes
@@ -908,7 +968,7 @@ svg_element!(
             /// This attribute defines the list of points (pairs of x,y absolute
             /// coordinates) required to draw the polygon. Value type: `<number>`+
             /// ; Default value: ""; Animatable: yes
-            points: String,
+            points: Points,
 
             /// This attribute lets specify the total length for the path, in
             /// user units. Value type: `<number>` ; Default value: none;
@@ -931,7 +991,7 @@ svg_element!(
             /// This attribute defines the list of points (pairs of x,y absolute
             /// coordinates) required to draw the polyline Value type: `<number>`+
             /// ; Default value: ""; Animatable: yes
-            points: String,
+            points: Points,
 
             /// This attribute lets specify the total length for the path, in
             /// user units. Value type: `<number>` ; Default value: none;
@@ -946,6 +1006,72 @@ parent_element!(polyline);
 impl<D: Dom> ConditionalProcessing for Polyline<D> {}
 impl<D: Dom> Presentation for Polyline<D> {}
 
+svg_element!(
+    /// The `<radialGradient>` element lets authors define radial gradients to
+    /// apply to other SVG elements. Reference it with
+    /// [`Paint::Url`][content_type::Paint::Url].
+    radial_gradient("radialGradient") = {
+        dom_type: web_sys::SvgRadialGradientElement;
+
+        attributes {
+            /// This attribute defines the coordinate system for attributes
+            /// cx, cy, r, fx, fy, fr. Value type: userSpaceOnUse|objectBoundingBox;
+            /// Default value: objectBoundingBox; Animatable: yes
+            gradient_units("gradientUnits"): String,
+
+            /// This attribute provides additional transformation to the
+            /// gradient coordinate system. Value type: `<transform-list>`;
+            /// Default value: identity transform; Animatable: yes
+            gradient_transform("gradientTransform"): Transform,
+
+            /// This attribute defines a reference to another gradient that will
+            /// be used as a template. Value type: `<URL>`; Default value: none;
+            /// Animatable: yes
+            href: String,
+
+            /// This attribute indicates how the gradient behaves if it starts
+            /// or ends inside the bounds of the shape containing the gradient.
+            /// Value type: pad|reflect|repeat; Default value: pad; Animatable:
+            /// yes
+            spread_method("spreadMethod"): String,
+
+            /// This attribute defines the x coordinate of the end circle of the
+            /// radial gradient. Value type: `<length>`; Default value: 50%;
+            /// Animatable: yes
+            cx: Length,
+
+            /// This attribute defines the y coordinate of the end circle of the
+            /// radial gradient. Value type: `<length>`; Default value: 50%;
+            /// Animatable: yes
+            cy: Length,
+
+            /// This attribute defines the radius of the start circle of the
+            /// radial gradient. Value type: `<length>`; Default value: 0%;
+            /// Animatable: yes
+            fr: Length,
+
+            /// This attribute defines the x coordinate of the start circle of
+            /// the radial gradient. Value type: `<length>`; Default value: Same
+            /// as cx; Animatable: yes
+            fx: Length,
+
+            /// This attribute defines the y coordinate of the start circle of
+            /// the radial gradient. Value type: `<length>`; Default value: Same
+            /// as cy; Animatable: yes
+            fy: Length,
+
+            /// This attribute defines the radius of the end circle of the
+            /// radial gradient. Value type: `<length>`; Default value: 50%;
+            /// Animatable: yes
+            r: Length,
+        };
+    }
+);
+
+parent_element!(radial_gradient);
+
+impl<D: Dom> Presentation for RadialGradient<D> {}
+
 svg_element!(
     /// The `<rect>` element is a basic SVG shape that draws rectangles, defined
     /// by their position, width, and height. The rectangles may have their
@@ -1111,7 +1237,7 @@ svg_element!(
             /// The SVG viewport coordinates for the current SVG fragment.
             /// Value type: `<list-of-numbers>` ; Default value: none;
             /// Animatable: yes
-            view_box("viewBox"): String,
+            view_box("viewBox"): ViewBox,
 
             /// The displayed width of the rectangular viewport. (Not the width
             /// of its coordinate system.) Value type: `<length>`|`<percentage>` ;
@@ -1180,7 +1306,7 @@ svg_element!(
             /// This attribute defines the bound of the SVG viewport for the
             /// current symbol. Value type: `<list-of-numbers>` ; Default value:
             /// none; Animatable: yes
-            view_box("viewBox"): String,
+            view_box("viewBox"): ViewBox,
 
             /// This attribute determines the width of the symbol. Value type:
             /// `<length>`|`<percentage>` ; Default value: auto; Animatable: yes
@@ -1393,7 +1519,7 @@ svg_element!(
         dom_type: web_sys::SvgViewElement;
 
         attributes {
-            view_box("viewBox"): String,
+            view_box("viewBox"): ViewBox,
             preserve_aspect_ratio("preserveAspectRatio"): String,
         };
     }
@@ -1,7 +1,7 @@
 //! Groups of SVG attributes.
 
 use super::{
-    content_type::{Length, NumberOrPercentage},
+    content_type::{Length, NumberOrPercentage, Paint, Transform},
     path::Data,
 };
 use crate::node::element::Element;
@@ -240,7 +240,7 @@ pub trait Presentation: Element {
         dominant_baseline: String,
         /// It defines the color of the inside of the graphical element it
         /// applies to. Value: `<paint>`; Animatable: Yes
-        fill: String,
+        fill: Paint,
         /// It specifies the opacity of the color or the content the current
         /// object is filled with. Value: `<number>`|`<percentage>`; Animatable:
         /// Yes
@@ -342,7 +342,7 @@ pub trait Presentation: Element {
         stop_opacity: String,
         /// Defines the color used to paint the outline of the shape. Value:
         /// `<paint>`; Animatable: Yes
-        stroke: String,
+        stroke: Paint,
         /// Defines the pattern of dashes and gaps used to paint the outline of
         /// the shape. Value: none|`<dasharray>`; Animatable: Yes
         stroke_dasharray: String,
@@ -377,7 +377,7 @@ pub trait Presentation: Element {
         /// Defines a list of transform definitions that are applied to an
         /// element and the element's children. Value: `<transform-list>`;
         /// Animatable: Yes
-        transform: String,
+        transform: Transform,
         /// - Value:; Animatable: -
         unicode_bidi: String,
         /// Specifies the vector effect to use when drawing an object. Value:
@@ -2,7 +2,7 @@
 //!
 //! See [MDN SVG Content Types](https://developer.mozilla.org/en-US/docs/Web/SVG/Content_type)
 
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt::Write};
 
 use silkenweb_signals_ext::value::Value;
 
@@ -82,3 +82,265 @@ impl AsAttribute<AutoOrLength> for Auto {}
 impl AsAttribute<AutoOrLength> for f64 {}
 impl AsAttribute<AutoOrLength> for Length {}
 impl AsAttribute<AutoOrLength> for Percentage {}
+
+/// An SVG [`<transform-list>`], for attributes like [`transform`].
+///
+/// Strings can still be used where a `Transform` is expected.
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb::{dom::Dry, prelude::*};
+/// # use svg::{attributes::Presentation, content_type::Transform, rect, Rect};
+/// let rect: Rect<Dry> = rect().transform(Transform::new().translate(10.0, 20.0).rotate(45.0));
+///
+/// assert_eq!(
+///     r#"<rect transform="translate(10 20) rotate(45)"></rect>"#,
+///     rect.freeze().to_string()
+/// );
+/// ```
+///
+/// [`<transform-list>`]: https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/transform
+/// [`transform`]: super::attributes::Presentation::transform
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transform(String);
+
+impl Transform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move by `x` and `y`.
+    pub fn translate(self, x: f64, y: f64) -> Self {
+        self.function("translate", &[x, y])
+    }
+
+    /// Rotate by `angle` degrees about the origin.
+    pub fn rotate(self, angle: f64) -> Self {
+        self.function("rotate", &[angle])
+    }
+
+    /// Rotate by `angle` degrees about the point (`x`, `y`).
+    pub fn rotate_about(self, angle: f64, x: f64, y: f64) -> Self {
+        self.function("rotate", &[angle, x, y])
+    }
+
+    /// Scale by `x` horizontally and `y` vertically.
+    pub fn scale(self, x: f64, y: f64) -> Self {
+        self.function("scale", &[x, y])
+    }
+
+    /// Skew by `angle` degrees along the x axis.
+    pub fn skew_x(self, angle: f64) -> Self {
+        self.function("skewX", &[angle])
+    }
+
+    /// Skew by `angle` degrees along the y axis.
+    pub fn skew_y(self, angle: f64) -> Self {
+        self.function("skewY", &[angle])
+    }
+
+    /// Apply the transformation matrix `[a c e] [b d f] [0 0 1]`.
+    pub fn matrix(self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        self.function("matrix", &[a, b, c, d, e, f])
+    }
+
+    fn function(mut self, name: &str, args: &[f64]) -> Self {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+
+        self.0.push_str(name);
+        self.0.push('(');
+        write_numbers(&mut self.0, args.iter().copied(), ' ');
+        self.0.push(')');
+        self
+    }
+}
+
+impl Attribute for Transform {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(&self.0))
+    }
+}
+
+impl Value for Transform {}
+
+impl AsAttribute<Transform> for Transform {}
+impl AsAttribute<Transform> for String {}
+impl<'a> AsAttribute<Transform> for &'a str {}
+impl<'a> AsAttribute<Transform> for &'a String {}
+
+/// The [`viewBox`] of an SVG viewport, in user space.
+///
+/// [`viewBox`]: https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/viewBox
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ViewBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ViewBox {
+    pub fn new(min_x: f64, min_y: f64, width: f64, height: f64) -> Self {
+        Self {
+            min_x,
+            min_y,
+            width,
+            height,
+        }
+    }
+}
+
+impl Attribute for ViewBox {
+    fn text(&self) -> Option<Cow<str>> {
+        let mut text = String::new();
+        write_numbers(
+            &mut text,
+            [self.min_x, self.min_y, self.width, self.height],
+            ' ',
+        );
+        Some(text.into())
+    }
+}
+
+impl Value for ViewBox {}
+
+impl AsAttribute<ViewBox> for ViewBox {}
+impl AsAttribute<ViewBox> for String {}
+impl<'a> AsAttribute<ViewBox> for &'a str {}
+impl<'a> AsAttribute<ViewBox> for &'a String {}
+
+/// A list of points for [`polygon`] and [`polyline`].
+///
+/// # Example
+///
+/// ```
+/// # use silkenweb::{dom::Dry, prelude::*};
+/// # use svg::{content_type::Points, polyline, Polyline};
+/// let line: Polyline<Dry> = polyline().points(Points::from_iter([(0.0, 0.0), (10.0, 5.0)]));
+///
+/// assert_eq!(
+///     r#"<polyline points="0,0 10,5"></polyline>"#,
+///     line.freeze().to_string()
+/// );
+/// ```
+///
+/// [`polygon`]: super::polygon
+/// [`polyline`]: super::polyline
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Points(String);
+
+impl Points {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a point.
+    pub fn point(mut self, x: f64, y: f64) -> Self {
+        self.push(x, y);
+        self
+    }
+
+    fn push(&mut self, x: f64, y: f64) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+
+        write_numbers(&mut self.0, [x, y], ',');
+    }
+}
+
+impl FromIterator<(f64, f64)> for Points {
+    fn from_iter<T: IntoIterator<Item = (f64, f64)>>(iter: T) -> Self {
+        let mut points = Self::new();
+
+        for (x, y) in iter {
+            points.push(x, y);
+        }
+
+        points
+    }
+}
+
+impl Attribute for Points {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(Cow::from(&self.0))
+    }
+}
+
+impl Value for Points {}
+
+impl AsAttribute<Points> for Points {}
+impl AsAttribute<Points> for String {}
+impl<'a> AsAttribute<Points> for &'a str {}
+impl<'a> AsAttribute<Points> for &'a String {}
+
+/// An SVG [`<paint>`], for [`fill`] and [`stroke`].
+///
+/// Strings can still be used where a `Paint` is expected.
+///
+/// # Example
+///
+/// Fill with a gradient:
+///
+/// ```
+/// # use silkenweb::{dom::Dry, prelude::*};
+/// # use svg::{attributes::Presentation, content_type::Paint, rect, Rect};
+/// let rect: Rect<Dry> = rect().fill(Paint::Url("my-gradient".to_string()));
+///
+/// assert_eq!(
+///     r#"<rect fill="url(#my-gradient)"></rect>"#,
+///     rect.freeze().to_string()
+/// );
+/// ```
+///
+/// [`<paint>`]: https://developer.mozilla.org/en-US/docs/Web/SVG/Content_type#paint
+/// [`fill`]: super::attributes::Presentation::fill
+/// [`stroke`]: super::attributes::Presentation::stroke
+#[derive(Clone, Debug, PartialEq)]
+pub enum Paint {
+    /// Don't paint anything.
+    None,
+    /// The value of the `color` property.
+    CurrentColor,
+    /// The fill of the context element, for elements inside a `<marker>`.
+    ContextFill,
+    /// The stroke of the context element, for elements inside a `<marker>`.
+    ContextStroke,
+    /// A CSS color.
+    Color(String),
+    /// A paint server, such as a gradient or pattern, with this `id`.
+    Url(String),
+}
+
+impl Attribute for Paint {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(match self {
+            Paint::None => "none".into(),
+            Paint::CurrentColor => "currentcolor".into(),
+            Paint::ContextFill => "context-fill".into(),
+            Paint::ContextStroke => "context-stroke".into(),
+            Paint::Color(color) => color.into(),
+            Paint::Url(id) => format!("url(#{id})").into(),
+        })
+    }
+}
+
+impl Value for Paint {}
+
+impl AsAttribute<Paint> for Paint {}
+impl AsAttribute<Paint> for String {}
+impl<'a> AsAttribute<Paint> for &'a str {}
+impl<'a> AsAttribute<Paint> for &'a String {}
+
+fn write_numbers(text: &mut String, numbers: impl IntoIterator<Item = f64>, separator: char) {
+    for (index, number) in numbers.into_iter().enumerate() {
+        if index > 0 {
+            text.push(separator);
+        }
+
+        write!(text, "{number}").unwrap();
+    }
+}
@@ -177,6 +177,7 @@ pub async fn hydrate(id: &str, element: impl Into<GenericElement<Hydro, Const>>)
 
     let mount_point = mount_point(id);
     let wet_element = element.hydrate(&mount_point, &mut stats);
+    wet_element.mount();
     insert_element(wet_element);
 
     stats
@@ -72,16 +72,28 @@ pub use silkenweb_base::clone;
 use silkenweb_base::document as base_document;
 /// Define `&str` constants for each class in a CSS file.
 ///
-/// This defines 2 modules:
+/// This always defines `class` and `stylesheet` modules. The `var` and
+/// `animation` modules are opt-in, with the `export_vars` and
+/// `export_animations` [parameters](#parameters):
 ///
 /// - `mod class` with constants or functions (depending on `auto_mount`) for
 ///   each CSS class. For a CSS class called `my-css-class`, a constant called
 ///   `MY_CSS_CLASS` or a function called `my_css_class` will be defined.
+/// - With `export_vars`, `mod var` with constants or functions for each
+///   custom property. For a custom property called `--my-color`, a constant
+///   called `MY_COLOR` or a function called `my_color` will be defined, with
+///   the value `"--my-color"`.
+/// - With `export_animations`, `mod animation` with constants or functions
+///   for each `@keyframes` name.
 /// - `mod stylesheet` with:
 ///     - An `fn text() -> &'static str` that gets the content of the
 ///       stylesheet.
 ///     - An `fn mount()` that lazily calls [`DefaultDom::mount_in_head`] once,
-///       to ensure the stylesheet is in the head.
+///       to ensure the stylesheet is in the head. This does nothing if the
+///       stylesheet is [extracted](#extracting-css).
+///     - If `scoped` is specified, a `const SCOPE: &str` with the name of the
+///       scope attribute, and an `fn scoped<T>(f: impl FnOnce() -> T) -> T`
+///       that mounts the stylesheet and calls [`style::scoped`].
 ///
 /// The macro takes two forms. Firstly it can take a single string literal which
 /// is the path to the CSS/SCSS/SASS file. The path is relative to the
@@ -102,11 +114,16 @@ use silkenweb_base::document as base_document;
 ///     exclude_prefixes = ["excluded-"],
 ///     validate,
 ///     auto_mount,
+///     scoped,
+///     export_vars,
+///     export_animations,
 ///     transpile = (
 ///         minify,
 ///         pretty,
 ///         modules,
 ///         nesting,
+///         dashed_idents,
+///         source_map,
 ///         browsers = (
 ///             android = (1, 0, 0),
 ///             chrome = (1, 0, 0),
@@ -123,8 +140,12 @@ use silkenweb_base::document as base_document;
 /// ```
 ///
 /// All are optional, but one of `path` or `content` must be specified.
+/// `warn_unused` and `transpile = (tree_shake)` are also accepted, but need a
+/// build script (see [Unused classes](#unused-classes)).
 ///
-/// - `path` is the path to the CSS /SCSS/SASS file.
+/// - `path` is the path to the CSS /SCSS/SASS file. Files with a `.scss` or
+///   `.sass` extension are compiled with [grass], and any files they import
+///   are tracked as dependencies.
 /// - `content` is the css content.
 /// - `prefix`: only classes starting with `prefix` should be included. Their
 ///   Rust names will have the prefix stripped.
@@ -134,9 +155,20 @@ use silkenweb_base::document as base_document;
 /// - `exclude_prefixes`: a list of prefixes to exclude. No Rust constants will
 ///   be defined for a class starting with any of these prefixes.
 ///   `exclude_prefixes` takes precedence over `include_prefixes`.
-/// - `validate`: validate the CSS.
+/// - `validate`: validate the CSS. Errors point to the line and column in the
+///   stylesheet, and into the `content` literal where possible.
 /// - `auto_mount`: Generate a function for each CSS class that will call
 ///   `stylesheet::mount` before returning the class name.
+/// - `scoped`: Scope the stylesheet to elements created within
+///   `stylesheet::scoped`, without using shadow DOM. Each selector is
+///   rewritten to only match elements with an attribute named with a hash of
+///   the stylesheet, as Vue and Svelte do.
+/// - `warn_unused`: Warn about classes that aren't used by the crate. See
+///   [Unused classes](#unused-classes).
+/// - `export_vars`: Define a `var` module, with a constant for each custom
+///   property, named without the `--` prefix.
+/// - `export_animations`: Define an `animation` module, with a constant for
+///   each `@keyframes` rule.
 /// - `transpile`: transpile the CSS with [lightningcss].
 ///
 /// ## `transpile`
@@ -148,14 +180,70 @@ use silkenweb_base::document as base_document;
 /// - `pretty`: Pretty print the final output. This is the default unless minify
 ///   is specified.
 /// - `modules`: Enable [CSS Modules] to locally scope class identifiers, via
-///   [lightningcss]. Composition is unsupported.
+///   [lightningcss]. Each class will be the space separated list of the class
+///   and any classes it `composes`. Stylesheets referenced with `composes: ...
+///   from "<path>"` are mounted by `stylesheet::mount`. `<path>` is relative
+///   to the CSS file, or `$CARGO_MANIFEST_DIR` when using `content`.
+/// - `dashed_idents`: Locally scope custom properties when `modules` is
+///   specified. The names in `mod var` (see `export_vars`) will be the scoped
+///   names.
+/// - `tree_shake`: Remove rules for classes that aren't used by the crate. See
+///   [Unused classes](#unused-classes).
 /// - `nesting`: Allow CSS nesting.
+/// - `source_map`: Append an inline source map to the transpiled CSS, so
+///   browser dev tools can show the original location of each rule. This is
+///   most useful with `pretty`. For SCSS and Sass, it maps to the compiled CSS.
 /// - `browsers` is a comma seperated list of the minimum supported browser
 ///   versions. This will add vendor prefixes to the CSS from `stylesheet()`.
 ///   The version is a paranthesized `,` seperated string of major, minor, and
 ///   patch versions. For example, to support firefox 110  + and chrome 111+,
 ///   use `browsers =( firefox = (110, 0, 0), chrome = (111, 0, 0) )`.
 ///
+/// # Unused classes
+///
+/// `warn_unused` and `tree_shake` need to know which classes are used, so the
+/// crate's build script must call `silkenweb_base::css::record_used_classes`:
+///
+/// ```ignore
+/// fn main() {
+///     silkenweb_base::css::record_used_classes();
+/// }
+/// ```
+///
+/// This looks for paths ending in `class::<identifier>` in the crate's `src`
+/// directory, so a class is considered used if any stylesheet's class with
+/// the same Rust identifier is used. Classes that are excluded by `prefix`,
+/// `include_prefixes` or `exclude_prefixes`, or are named in `composes`, are
+/// always considered used. Classes used by other crates, through a `public`
+/// stylesheet, aren't detected, so don't use `tree_shake` on stylesheets
+/// shared between crates.
+///
+/// `tree_shake` minifies the stylesheet, as it relies on [lightningcss]'s
+/// minifier to remove unused rules. `warn_unused` warnings are reported as a
+/// use of a deprecated item, so they show up on a stable compiler.
+///
+/// # Extracting CSS
+///
+/// If the `SILKENWEB_CSS_EXTRACT_DIR` environment variable is set at compile
+/// time, each stylesheet, along with any stylesheets it `composes` from, is
+/// written to a file in that directory instead of being mounted at runtime.
+/// `stylesheet::mount` does nothing, so the stylesheet text isn't included in
+/// the binary unless `stylesheet::text` is used, for example for server side
+/// rendering. A build step can then concatenate the files into one stylesheet:
+///
+/// ```text
+/// SILKENWEB_CSS_EXTRACT_DIR="$PWD/target/css" trunk build
+/// cat target/css/*.css > dist/app.css
+/// ```
+///
+/// The path should be absolute. Files are named with the package name, the
+/// stylesheet's path relative to `CARGO_MANIFEST_DIR` (for `path`
+/// stylesheets) and a hash of the stylesheet, so concatenating them in name
+/// order, as `cat *.css` does, gives the same bundle for every build. Files
+/// aren't removed when a stylesheet changes, so clear the directory and do a
+/// clean build to remove stale stylesheets. Changing the environment variable
+/// rebuilds any crates that use `css!`.
+///
 /// # Examples
 ///
 /// Define private constants for all CSS classes:
@@ -183,6 +271,37 @@ use silkenweb_base::document as base_document;
 /// "#);
 /// ```
 /// 
+/// Custom properties and keyframes:
+///
+/// ```
+/// # use silkenweb_macros::css;
+/// css!(content = r#"
+///     :root {
+///         --brand-color: hotpink;
+///     }
+///
+///     @keyframes fade-in {
+///         from { opacity: 0; }
+///     }
+/// "#, export_vars, export_animations);
+/// assert_eq!(var::BRAND_COLOR, "--brand-color");
+/// assert_eq!(animation::FADE_IN, "fade-in");
+/// ```
+///
+/// Scope a stylesheet to some elements:
+///
+/// ```
+/// # use html::{p, P};
+/// # use silkenweb::{css, dom::Dry, prelude::*};
+/// css!(content = "p { color: hotpink; }", scoped);
+///
+/// let app: P<Dry> = stylesheet::scoped(|| p().text("Scoped"));
+/// let html = app.freeze().to_string();
+///
+/// assert_eq!(html, format!(r#"<p {}="">Scoped</p>"#, stylesheet::SCOPE));
+/// assert!(stylesheet::text().contains(&format!("p[{}]", stylesheet::SCOPE)));
+/// ```
+///
 /// Include classes starting with `border-`, except classes starting with
 /// `border-excluded-`:
 /// ```
@@ -210,9 +329,156 @@ use silkenweb_base::document as base_document;
 /// ```
 /// 
 /// [lightningcss]: https://lightningcss.dev/
+/// [grass]: https://github.com/connorskees/grass
 /// [`DefaultDom::mount_in_head`]: crate::dom::DefaultDom::mount_in_head
 /// [CSS Modules]: https://github.com/css-modules/css-modules
+/// [`style::scoped`]: crate::style::scoped
 pub use silkenweb_macros::css;
+/// Style an element with inline CSS.
+///
+/// `styled!(element, { ... })` adds a class to `element`, and mounts a
+/// stylesheet for that class with [`DefaultDom::mount_in_head`] the first time
+/// it's evaluated. The CSS is
+/// the contents of the braces, which can contain declarations and nested rules
+/// starting with `&`. It's validated at compile time.
+///
+/// Rust values can be interpolated with `${value}`. Each value is set as a
+/// custom property on the element, with [`Element::style_property`], so it
+/// can be anything that [`Element::style_property`] accepts, including
+/// signals and the types in [`style`].
+///
+/// The CSS must consist of valid Rust tokens, so strings must be double quoted.
+/// Whitespace is taken from the source code, which isn't always available
+/// to proc macros, for example in IDEs. Without it, whitespace is guessed from
+/// the tokens. Selectors starting with `&`, like `& .child` and `&:hover`, are
+/// still correct, but descendant selectors after an identifier, like
+/// `& p .note`, and whitespace sensitive values, like `calc(1px + 2px)`, may
+/// not be.
+///
+/// # Example
+///
+/// ```
+/// # use futures_signals::signal::Mutable;
+/// # use html::{div, Div};
+/// # use silkenweb::{dom::Dry, prelude::*, style::Length, styled, value::Sig};
+/// const SPACING: Length = Length::Rem(1.5);
+/// let color = Mutable::new("hotpink");
+///
+/// let app: Div<Dry> = styled!(div().text("Hello, world!"), {
+///     padding: ${SPACING};
+///     color: ${Sig(color.signal())};
+///
+///     &:hover {
+///         text-decoration: underline;
+///     }
+/// });
+/// ```
+///
+/// [`DefaultDom::mount_in_head`]: crate::dom::DefaultDom::mount_in_head
+/// [`Element::style_property`]: crate::node::element::Element::style_property
+pub use silkenweb_macros::styled;
+/// Build elements with HTML-like syntax.
+///
+/// `view!` expands to the typed element builders, so unknown elements,
+/// attributes and events are compile errors. It works with any [`Dom`], which
+/// is inferred as usual.
+///
+/// - `<name ...>` calls `elements::html::name()`. Use a path, like
+///   `<svg::rect>`, for other elements, including your own.
+/// - `attribute=value` calls the builder method `attribute(value)`. `value` is
+///   a literal, or an expression in braces, like `{Sig(signal)}`. `-` can be
+///   used in place of `_`, so `aria-label` calls `aria_label`. Without a value,
+///   the attribute is set to `true`.
+/// - A literal `class`, like `class="btn primary"`, is split on whitespace.
+/// - `on:event={handler}` calls `on_event(handler)`.
+/// - String literal children are text.
+/// - `{ expression }` children are either nodes or text, including signals of
+///   either.
+///
+/// There must be a single root element.
+///
+/// # Example
+///
+/// ```
+/// # use futures_signals::signal::Mutable;
+/// # use html::Div;
+/// # use silkenweb::{dom::Dry, prelude::*, view};
+/// let count = Mutable::new(0);
+/// let count_text = count.signal_ref(|c| c.to_string());
+/// let app: Div<Dry> = view! {
+///     <div class="counter">
+///         <button type="button" on:click={move |_, _| count.replace_with(|c| *c + 1)}>
+///             "+"
+///         </button>
+///         <p>"Count: " {Sig(count_text)}</p>
+///     </div>
+/// };
+/// ```
+///
+/// [`Dom`]: crate::dom::Dom
+pub use silkenweb_macros::view;
+/// Derive a [`Template`] from a component function.
+///
+/// The function must have a single `param: &Param` argument, and return an
+/// element. Builder methods with arguments that use `param` are run when the
+/// template is instantiated, with [`on_instantiate`]. Everything else is built
+/// once, the first time the function is called, and cloned for each call
+/// after that. Template functions are useful for rows in `children_signal`,
+/// where the same structure is built many times.
+///
+/// As the template is only built once:
+///
+/// - The structure can't depend on `param`. For example, `if param.active {
+///   div() } else { span() }` won't compile. Use `param` in builder method
+///   arguments.
+/// - Statements before the final element expression can't use `param`.
+/// - Builder method arguments are moved into a closure, so event handlers
+///   should copy what they need from `param` first, like `{ let id = param.id;
+///   move |_, _| select(id) }`.
+///
+/// # Example
+///
+/// ```
+/// # use futures_signals::{
+/// #     signal::Mutable,
+/// #     signal_vec::{MutableVec, SignalVecExt},
+/// # };
+/// # use html::{tbody, td, tr, Tbody, Tr};
+/// # use silkenweb::{
+/// #     dom::{Dry, InstantiableDom},
+/// #     prelude::*,
+/// #     template,
+/// # };
+/// #[derive(Clone)]
+/// struct Row {
+///     id: usize,
+///     label: Mutable<String>,
+/// }
+///
+/// #[template]
+/// fn table_row<D: InstantiableDom>(row: &Row) -> Tr<D> {
+///     tr().class("row")
+///         .child(td().class("id").text(row.id.to_string()))
+///         .child(td().text(Sig(row.label.signal_cloned())))
+/// }
+///
+/// let rows = MutableVec::new_with_values(vec![
+///     Row {
+///         id: 1,
+///         label: Mutable::new("First".to_string()),
+///     },
+///     Row {
+///         id: 2,
+///         label: Mutable::new("Second".to_string()),
+///     },
+/// ]);
+/// let table: Tbody<Dry> =
+///     tbody().children_signal(rows.signal_vec_cloned().map(|r| table_row(&r)));
+/// ```
+///
+/// [`Template`]: crate::dom::Template
+/// [`on_instantiate`]: crate::node::element::GenericElement::on_instantiate
+pub use silkenweb_macros::template;
 /// Derive the traits needed for a blanket implmenetation of [`ChildElement`].
 ///
 /// This only works for structs. It will defer to one field for the
@@ -284,6 +550,8 @@ pub mod macros;
 
 pub mod animation;
 pub mod attribute;
+pub mod context;
+pub mod custom_element;
 pub mod document;
 pub mod dom;
 pub mod elements;
@@ -291,6 +559,7 @@ pub mod hydration;
 pub mod node;
 pub mod router;
 pub mod storage;
+pub mod style;
 pub mod task;
 
 /// Commonly used imports, all in one place.
@@ -302,7 +571,7 @@ pub mod prelude {
 
     pub use crate::{
         clone,
-        elements::{html, svg, AriaElement, ElementEvents, HtmlElement, HtmlElementEvents},
+        elements::{html, mathml, svg, AriaElement, ElementEvents, HtmlElement, HtmlElementEvents},
         mount,
         node::{
             element::{Element, ParentElement, ShadowRootParent},
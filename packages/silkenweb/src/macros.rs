@@ -1,10 +1,94 @@
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    thread::LocalKey,
+};
+
 pub use futures_signals::{signal::Signal, signal_vec::SignalVec};
 pub use paste::paste;
 pub use silkenweb_macros::rust_to_html_ident;
+use silkenweb_signals_ext::value::{RefSignalOrValue, SignalOrValue, Value};
 pub use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 pub use web_sys;
 
 pub use crate::intern_str;
+use crate::{
+    dom::Dom,
+    node::{element::ParentElement, Node},
+};
+
+/// A `{ expression }` child in the [`view`][crate::view] macro.
+///
+/// `Marker` is inferred, depending on whether the child is a node or text.
+pub trait ViewChild<'a, D: Dom, Marker> {
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P;
+}
+
+/// [`ViewChild`] marker for nodes, or signals of nodes.
+pub struct NodeChild;
+
+/// [`ViewChild`] marker for text, or signals of text.
+pub struct TextChild;
+
+impl<D, T, N> ViewChild<'static, D, NodeChild> for T
+where
+    D: Dom,
+    T: SignalOrValue<Item = N>,
+    N: Value + Into<Node<D>> + 'static,
+{
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P {
+        parent.child(self)
+    }
+}
+
+impl<'a, D, T, S> ViewChild<'a, D, TextChild> for T
+where
+    D: Dom,
+    T: RefSignalOrValue<'a, Item = S>,
+    S: 'a + AsRef<str> + Into<String>,
+{
+    fn add_to<P: ParentElement<D>>(self, parent: P) -> P {
+        parent.text(self)
+    }
+}
+
+/// The templates for a [`template`][crate::template] function.
+///
+/// A function can be generic over its [`Dom`], so there's a template for each
+/// template type.
+#[derive(Default)]
+pub struct TemplateCache(HashMap<TypeId, Box<dyn Any>>);
+
+impl TemplateCache {
+    /// Instantiate the cached template, building it first if this is the first
+    /// call.
+    pub fn instantiate<T: 'static, R>(
+        cache: &'static LocalKey<RefCell<Self>>,
+        build: impl FnOnce() -> T,
+        instantiate: impl FnOnce(&T) -> R,
+    ) -> R {
+        let key = TypeId::of::<T>();
+
+        if !cache.with(|cache| cache.borrow().0.contains_key(&key)) {
+            // Don't hold a borrow while building, as `build` might use other
+            // templates.
+            let template = build();
+            cache.with(|cache| cache.borrow_mut().0.insert(key, Box::new(template)));
+        }
+
+        // Only a shared borrow is held while instantiating, so initialization
+        // functions can use this template again.
+        cache.with(|cache| {
+            let cache = cache.borrow();
+            let template = cache.0[&key]
+                .downcast_ref()
+                .expect("Template should have the type it was keyed by");
+
+            instantiate(template)
+        })
+    }
+}
 
 /// Define a custom html element.
 ///
@@ -159,6 +243,54 @@ macro_rules! svg_attribute_doc {
     };
 }
 
+macro_rules! mathml_element {
+    (
+        $(#[$elem_meta:meta])*
+        $name:ident $( ($text_name: literal) )? = {
+            $($tail:tt)*
+        }
+    ) => {
+        $crate::dom_element!(
+            $(#[$elem_meta])*
+            $name $( ($text_name) )? = {
+                common_attributes = [$crate::elements::mathml::Global, $crate::elements::AriaElement];
+                common_events = [];
+                namespace = $crate::node::element::Namespace::MathML;
+                doc_macro = mathml_element_doc;
+                attribute_doc_macro = mathml_attribute_doc;
+                $($tail)*
+            }
+        );
+    }
+}
+
+macro_rules! mathml_element_doc {
+    ($prefix:literal, $name:expr) => {
+        concat!(
+            $prefix,
+            " MathML [`",
+            $name,
+            "`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/",
+            $name,
+            ") element"
+        )
+    };
+}
+
+macro_rules! mathml_attribute_doc {
+    ($element:expr, $name:expr) => {
+        concat!(
+            "The MathML [`",
+            $name,
+            "`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/",
+            $element,
+            "#",
+            $name,
+            ") attribute"
+        )
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! dom_element {
@@ -303,6 +435,14 @@ macro_rules! dom_element {
                 Self(self.0.attribute(name, value))
             }
 
+            fn style_property<'a>(
+                self,
+                name: &str,
+                value: impl $crate::value::RefSignalOrValue<'a, Item = impl $crate::attribute::Attribute>
+            ) -> Self {
+                Self(self.0.style_property(name, value))
+            }
+
             fn effect(self, f: impl ::std::ops::FnOnce(&Self::DomType) + 'static) -> Self {
                 Self(self.0.effect(|elem| {
                     f($crate::macros::UnwrapThrowExt::unwrap_throw($crate::macros::JsCast::dyn_ref(elem)))
@@ -340,6 +480,30 @@ macro_rules! dom_element {
             ) -> Self {
                 Self($crate::node::element::Element::on(self.0, name, f))
             }
+
+            fn on_mount(self, mut f: impl FnMut(&Self::DomType) + 'static) -> Self {
+                Self(self.0.on_mount(move |elem| {
+                    f($crate::macros::UnwrapThrowExt::unwrap_throw($crate::macros::JsCast::dyn_ref(elem)))
+                }))
+            }
+
+            fn on_unmount(self, mut f: impl FnMut(&Self::DomType) + 'static) -> Self {
+                Self(self.0.on_unmount(move |elem| {
+                    f($crate::macros::UnwrapThrowExt::unwrap_throw($crate::macros::JsCast::dyn_ref(elem)))
+                }))
+            }
+
+            fn on_cleanup(self, f: impl FnOnce() + 'static) -> Self {
+                Self(self.0.on_cleanup(f))
+            }
+
+            fn provide_context<T: 'static>(self, value: T) -> Self {
+                Self(self.0.provide_context(value))
+            }
+
+            fn use_context<T: Clone + 'static>(&self) -> $crate::context::ContextSignal<T> {
+                self.0.use_context()
+            }
         }
 
         impl<Dom: $crate::dom::Dom, Mutability> $crate::value::Value
@@ -488,6 +652,38 @@ macro_rules! shadow_parent_element {
                 {
                     [< $name:camel >] (self.0.attach_shadow_children(children))
                 }
+
+                fn shadow_root_options(
+                    self,
+                    options: $crate::node::element::ShadowRootOptions
+                ) -> Self {
+                    [< $name:camel >] (self.0.shadow_root_options(options))
+                }
+
+                fn adopt_stylesheet(self, css: &str) -> Self {
+                    [< $name:camel >] (self.0.adopt_stylesheet(css))
+                }
+            }
+        }
+    };
+}
+
+/// Export a function to define a [`CustomElement`] from Javascript.
+///
+/// `define_custom_element!(MyElement)` exports `define_my_element` with
+/// `wasm_bindgen`, which calls [`custom_element::define`]. This lets
+/// Javascript code decide when to register the element. The crate must depend
+/// on `wasm-bindgen`.
+///
+/// [`CustomElement`]: crate::custom_element::CustomElement
+/// [`custom_element::define`]: crate::custom_element::define
+#[macro_export]
+macro_rules! define_custom_element {
+    ($name:ident) => {
+        $crate::macros::paste! {
+            #[::wasm_bindgen::prelude::wasm_bindgen]
+            pub fn [< define_ $name:snake >]() {
+                $crate::custom_element::define::<$name>()
             }
         }
     };
@@ -4,12 +4,17 @@ use std::fmt;
 
 use silkenweb_signals_ext::value::Value;
 
-use crate::dom::{
-    private::{DomText, EventStore},
-    DefaultDom, Dom, InDom,
+use self::lifecycle::Lifecycle;
+use crate::{
+    context::Context,
+    dom::{
+        private::{DomText, EventStore},
+        DefaultDom, Dom, InDom,
+    },
 };
 
 mod component;
+mod lifecycle;
 
 pub mod element;
 
@@ -20,6 +25,8 @@ pub struct Node<D: Dom = DefaultDom> {
     node: D::Node,
     resources: ResourceVec,
     events: EventStore,
+    lifecycle: Lifecycle,
+    context: Context,
 }
 
 impl<D: Dom> Node<D> {
@@ -44,6 +51,8 @@ impl<D: Dom> From<Text<D>> for Node<D> {
             node: text.0.into(),
             resources: Vec::new(),
             events: EventStore::default(),
+            lifecycle: Lifecycle::default(),
+            context: Context::default(),
         }
     }
 }
@@ -1,14 +1,19 @@
+use futures_signals::{
+    signal::{Signal, SignalExt},
+    signal_vec::{SignalVec, SignalVecExt},
+};
+
 use super::{
-    element::{Const, GenericElement, ParentElement, ShadowRootParent},
+    element::{Const, GenericElement, ParentElement, ShadowRootOptions, ShadowRootParent},
     ChildNode, Node,
 };
 use crate::{
     dom::{DefaultDom, InDom, InstantiableDom},
     elements::{
-        html::{div, slot, style, Div, Slot},
+        html::{div, slot, Slot},
         HtmlElement,
     },
-    value::Value,
+    value::{Sig, Value},
 };
 
 /// A lightweight type to encapsulate HTML and CSS using shadow DOM.
@@ -20,6 +25,12 @@ use crate::{
 /// slot, which should be added to the shadow DOM with the
 /// [`child`][`Self::child`] or [`children`][`Self::children`] methods.
 ///
+/// [`slot_signal`][`Self::slot_signal`] and
+/// [`multi_slot_signal`][`Self::multi_slot_signal`] do the same for reactive
+/// light DOM children, and [`child_signal`][`Self::child_signal`] and
+/// [`children_signal`][`Self::children_signal`] add reactive shadow DOM
+/// children.
+///
 /// # Example
 ///
 /// This creates a `<div>` with an open shadow root attached. The light DOM
@@ -51,7 +62,7 @@ use crate::{
 ///
 /// [Using Shadow DOM]: https://developer.mozilla.org/en-US/docs/Web/Web_Components/Using_shadow_DOM
 pub struct Component<D: InstantiableDom = DefaultDom> {
-    element: Option<Div<D>>,
+    element: Option<GenericElement<D>>,
     id: usize,
 }
 
@@ -59,14 +70,36 @@ impl<D: InstantiableDom> Component<D> {
     /// Constructor
     pub fn new() -> Self {
         Self {
-            element: Some(div()),
+            element: Some(div().into()),
+            id: 0,
+        }
+    }
+
+    /// Construct a [`Component`] with a shadow root that uses `options`.
+    pub fn with_options(options: ShadowRootOptions) -> Self {
+        Self {
+            element: Some(GenericElement::from(div()).shadow_root_options(options)),
             id: 0,
         }
     }
 
-    /// Constuct a [`Component`] with a `<style>` element in the shadow DOM.
+    /// Constuct a [`Component`] with a stylesheet in the shadow DOM.
+    ///
+    /// See [`Self::adopt_stylesheet`].
     pub fn styled(css: &str) -> Self {
-        Self::new().child(style().text(css))
+        Self::new().adopt_stylesheet(css)
+    }
+
+    /// Add a stylesheet to the shadow DOM.
+    ///
+    /// In the browser, this uses a constructable stylesheet that's shared by
+    /// every component with the same `css`, so it's only parsed once. See
+    /// [`ShadowRootParent::adopt_stylesheet`].
+    pub fn adopt_stylesheet(self, css: &str) -> Self {
+        Self {
+            element: self.element.map(|elem| elem.adopt_stylesheet(css)),
+            id: self.id,
+        }
     }
 
     /// Add `child` to the light DOM.
@@ -96,6 +129,42 @@ impl<D: InstantiableDom> Component<D> {
         slot().name(id)
     }
 
+    /// Add a reactive `child` to the light DOM.
+    ///
+    /// Each new value of `child` replaces the previous one in the slot.
+    pub fn slot_signal<E>(&mut self, child: impl Signal<Item = E> + 'static) -> Slot<D>
+    where
+        E: HtmlElement + ChildNode<D>,
+    {
+        let id = self.new_id();
+        let slot_name = id.clone();
+        self.element = Some(
+            self.element
+                .take()
+                .unwrap()
+                .child(Sig(child.map(move |child| child.slot(&slot_name)))),
+        );
+        slot().name(id)
+    }
+
+    /// Add reactive `children` to the light DOM.
+    ///
+    /// All the children are assigned to the same slot.
+    pub fn multi_slot_signal<E>(&mut self, children: impl SignalVec<Item = E> + 'static) -> Slot<D>
+    where
+        E: HtmlElement + ChildNode<D>,
+    {
+        let id = self.new_id();
+        let slot_name = id.clone();
+        self.element = Some(
+            self.element
+                .take()
+                .unwrap()
+                .children_signal(children.map(move |child| child.slot(&slot_name))),
+        );
+        slot().name(id)
+    }
+
     /// Add `child` to the shadow DOM.
     pub fn child(self, child: impl ChildNode<D>) -> Self {
         Self {
@@ -119,6 +188,24 @@ impl<D: InstantiableDom> Component<D> {
         }
     }
 
+    /// Add a reactive `child` to the shadow DOM.
+    pub fn child_signal(self, child: impl Signal<Item = impl ChildNode<D>> + 'static) -> Self {
+        self.children_signal(child.map(|child| vec![child]).to_signal_vec())
+    }
+
+    /// Add reactive `children` to the shadow DOM.
+    pub fn children_signal<N>(self, children: impl SignalVec<Item = N> + 'static) -> Self
+    where
+        N: Into<Node<D>>,
+    {
+        Self {
+            element: self
+                .element
+                .map(|elem| elem.shadow_children_signal(children)),
+            id: self.id,
+        }
+    }
+
     fn new_id(&mut self) -> String {
         let id = self.id.to_string();
         self.id += 1;
@@ -140,13 +227,13 @@ impl<D: InstantiableDom> InDom for Component<D> {
 
 impl<D: InstantiableDom> From<Component<D>> for GenericElement<D> {
     fn from(value: Component<D>) -> Self {
-        value.element.unwrap().into()
+        value.element.unwrap()
     }
 }
 
 impl<D: InstantiableDom> From<Component<D>> for GenericElement<D, Const> {
     fn from(value: Component<D>) -> Self {
-        value.element.unwrap().into()
+        value.element.unwrap().freeze()
     }
 }
 
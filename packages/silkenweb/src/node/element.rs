@@ -15,7 +15,7 @@ use discard::DiscardOnDrop;
 use futures_signals::{
     cancelable_future,
     signal::{Signal, SignalExt},
-    signal_vec::{always, SignalVec, SignalVecExt},
+    signal_vec::{always, SignalVec, SignalVecExt, VecDiff},
     CancelableFutureHandle,
 };
 use silkenweb_base::{clone, document};
@@ -23,9 +23,13 @@ use silkenweb_signals_ext::value::{Executor, RefSignalOrValue, SignalOrValue, Va
 use wasm_bindgen::{JsCast, JsValue};
 
 use self::child_vec::ChildVec;
-use super::{ChildNode, Node, ResourceVec};
+use super::{
+    lifecycle::{Cleanup, Lifecycle},
+    ChildNode, Node, ResourceVec,
+};
 use crate::{
     attribute::Attribute,
+    context::{Context, ContextSignal},
     dom::{
         private::{DomElement, DomText, EventStore, InstantiableDomElement},
         DefaultDom, Dom, Hydro, InDom, InstantiableDom, Template, Wet,
@@ -34,11 +38,16 @@ use crate::{
     hydration::HydrationStats,
     intern_str,
     node::text,
-    task,
+    style, task,
 };
 
 mod child_vec;
 
+/// Recorded in `GenericElement::attributes` when `style_property` is used. It
+/// can't clash with a real attribute name, as it contains a space.
+#[cfg(debug_assertions)]
+const STYLE_PROPERTY_MARKER: &str = "style property";
+
 /// A generic HTML element.
 ///
 /// Where available, specific DOM elements from [`crate::elements::html`] should
@@ -48,18 +57,38 @@ mod child_vec;
 pub struct GenericElement<D: Dom = DefaultDom, Mutability = Mut> {
     static_child_count: usize,
     child_vec: Option<Pin<Box<dyn SignalVec<Item = Node<D>>>>>,
+    static_shadow_child_count: usize,
+    shadow_child_vec: Option<ShadowChildVec<D>>,
     resources: ResourceVec,
     events: EventStore,
+    lifecycle: Lifecycle,
     element: D::Element,
+    context: Context,
     #[cfg(debug_assertions)]
     attributes: HashSet<String>,
     phantom: PhantomData<Mutability>,
 }
 
+/// Reactive shadow root children, and a function to apply their updates to the
+/// shadow root.
+type ShadowChildVec<D> = (
+    Pin<Box<dyn SignalVec<Item = Node<D>>>>,
+    Rc<RefCell<dyn FnMut(VecDiff<Node<D>>)>>,
+);
+
 impl<D: Dom> GenericElement<D> {
     /// Construct an element with type `tag` in `namespace`.
+    ///
+    /// If it's created within [`style::scoped`], the element is marked with the
+    /// scope attribute.
     pub fn new(namespace: Namespace, tag: &str) -> Self {
-        Self::from_dom(D::Element::new(namespace, tag), 0)
+        let mut element = D::Element::new(namespace, tag);
+
+        if let Some(scope) = style::current_scope() {
+            element.attribute(scope, "");
+        }
+
+        Self::from_dom(element, 0)
     }
 
     /// Make this element immutable.
@@ -68,9 +97,13 @@ impl<D: Dom> GenericElement<D> {
         GenericElement {
             static_child_count: self.static_child_count,
             child_vec: self.child_vec,
+            static_shadow_child_count: self.static_shadow_child_count,
+            shadow_child_vec: self.shadow_child_vec,
             resources: self.resources,
             events: self.events,
+            lifecycle: self.lifecycle,
             element: self.element,
+            context: self.context,
             #[cfg(debug_assertions)]
             attributes: self.attributes,
             phantom: PhantomData,
@@ -81,9 +114,13 @@ impl<D: Dom> GenericElement<D> {
         Self {
             static_child_count,
             child_vec: None,
+            static_shadow_child_count: 0,
+            shadow_child_vec: None,
             resources: Vec::new(),
             events: EventStore::default(),
+            lifecycle: Lifecycle::default(),
             element,
+            context: Context::default(),
             #[cfg(debug_assertions)]
             attributes: HashSet::new(),
             phantom: PhantomData,
@@ -94,14 +131,48 @@ impl<D: Dom> GenericElement<D> {
         child.build();
         self.resources.append(&mut child.resources);
         self.events.combine(child.events);
+        self.lifecycle.combine(child.lifecycle);
+        self.context.add_child(&child.context);
+    }
+
+    /// Set the value of a `select` element from a signal.
+    ///
+    /// Unlike setting the value in an effect, this marks the matching
+    /// `option`s as `selected` when rendering to a string.
+    pub(crate) fn select_value(mut self, value: impl Signal<Item = String> + 'static) -> Self {
+        let mut element = self.element.clone();
+
+        self.spawn(value.for_each(move |value| {
+            element.select_value(&value);
+            async {}
+        }));
+
+        self
     }
 
     fn check_attribute_unique(&mut self, name: &str) {
         #[cfg(debug_assertions)]
-        debug_assert!(self.attributes.insert(name.into()));
+        {
+            debug_assert!(self.attributes.insert(name.into()));
+            debug_assert!(
+                name != "style" || !self.attributes.contains(STYLE_PROPERTY_MARKER),
+                "The `style` attribute can't be used with `style_property`"
+            );
+        }
         let _ = name;
     }
 
+    fn check_style_property(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            debug_assert!(
+                !self.attributes.contains("style"),
+                "`style_property` can't be used with the `style` attribute"
+            );
+            self.attributes.insert(STYLE_PROPERTY_MARKER.into());
+        }
+    }
+
     fn class_signal<T>(
         element: &mut D::Element,
         class: T,
@@ -153,6 +224,8 @@ impl<D: Dom, Mutability> GenericElement<D, Mutability> {
             let child_vec = Rc::new(RefCell::new(ChildVec::new(
                 self.element.clone(),
                 self.static_child_count,
+                self.lifecycle.shared(),
+                self.context.shared(),
             )));
 
             let future = children.for_each({
@@ -169,6 +242,19 @@ impl<D: Dom, Mutability> GenericElement<D, Mutability> {
             self.resources.push(Box::new(resource));
         }
 
+        if let Some((children, child_vec)) = self.shadow_child_vec.take() {
+            let future = children.for_each({
+                clone!(child_vec);
+                move |update| {
+                    (*child_vec.borrow_mut())(update);
+                    async {}
+                }
+            });
+
+            let resource = (child_vec, spawn_cancelable_future(future));
+            self.resources.push(Box::new(resource));
+        }
+
         // This improves memory usage, and doesn't detectably impact performance
         self.resources.shrink_to_fit();
     }
@@ -234,6 +320,8 @@ impl<D: Dom> ParentElement<D> for GenericElement<D> {
                     parent.element.append_child(child.as_node());
                     parent.resources.extend(child.resources);
                     parent.events.combine(child.events);
+                    parent.lifecycle.combine(child.lifecycle);
+                    parent.context.add_child(&child.context);
                 }
 
                 parent
@@ -289,6 +377,16 @@ impl<Mutability> GenericElement<Wet, Mutability> {
     pub(crate) fn dom_element(&self) -> web_sys::Element {
         self.element.dom_element()
     }
+
+    /// Run `on_mount` callbacks for this element and its descendants.
+    pub(crate) fn mount(&self) {
+        self.lifecycle.mount();
+    }
+
+    /// Run `on_unmount` callbacks for this element and its descendants.
+    pub(crate) fn unmount(&self) {
+        self.lifecycle.unmount();
+    }
 }
 
 impl<Mutability> GenericElement<Hydro, Mutability> {
@@ -302,9 +400,13 @@ impl<Mutability> GenericElement<Hydro, Mutability> {
         GenericElement {
             static_child_count: self.static_child_count,
             child_vec: None,
+            static_shadow_child_count: self.static_shadow_child_count,
+            shadow_child_vec: None,
             resources: self.resources,
             events: self.events,
+            lifecycle: self.lifecycle,
             element: self.element.hydrate(element, tracker),
+            context: self.context,
             #[cfg(debug_assertions)]
             attributes: self.attributes,
             phantom: PhantomData,
@@ -312,13 +414,77 @@ impl<Mutability> GenericElement<Hydro, Mutability> {
     }
 }
 
+impl<D: InstantiableDom> GenericElement<D> {
+    /// Add reactive `children` to the shadow root, after any existing shadow
+    /// root children.
+    pub(crate) fn shadow_children_signal<N>(
+        mut self,
+        children: impl SignalVec<Item = N> + 'static,
+    ) -> Self
+    where
+        N: Into<Node<D>>,
+    {
+        let new_children = children.map(|child| child.into());
+
+        let shadow_child_vec = if let Some((child_vec, apply_update)) = self.shadow_child_vec.take()
+        {
+            (child_vec.chain(new_children).boxed_local(), apply_update)
+        } else {
+            let mut child_vec = ChildVec::<D, _>::new(
+                self.element.shadow_root_parent(),
+                self.static_shadow_child_count,
+                self.lifecycle.shared(),
+                self.context.shared(),
+            );
+            let apply_update: Rc<RefCell<dyn FnMut(VecDiff<Node<D>>)>> =
+                Rc::new(RefCell::new(move |update: VecDiff<Node<D>>| {
+                    child_vec.apply_update(update)
+                }));
+
+            (new_children.boxed_local(), apply_update)
+        };
+
+        self.shadow_child_vec = Some(shadow_child_vec);
+        self
+    }
+}
+
 impl<D: InstantiableDom> ShadowRootParent<D> for GenericElement<D> {
     fn attach_shadow_children<N>(mut self, children: impl IntoIterator<Item = N> + 'static) -> Self
     where
         N: Into<Node<D>>,
     {
-        self.element
-            .attach_shadow_children(children.into_iter().map(|child| child.into().into_node()));
+        if self.shadow_child_vec.is_some() {
+            let children = children
+                .into_iter()
+                .map(|node| node.into())
+                .collect::<Vec<_>>();
+            return self.shadow_children_signal(always(children));
+        }
+
+        let children = children
+            .into_iter()
+            .map(|child| {
+                let child = child.into();
+                self.resources.extend(child.resources);
+                self.events.combine(child.events);
+                self.lifecycle.combine(child.lifecycle);
+                self.context.add_child(&child.context);
+                child.node
+            })
+            .collect::<Vec<_>>();
+        self.static_shadow_child_count += children.len();
+        self.element.attach_shadow_children(children);
+        self
+    }
+
+    fn shadow_root_options(mut self, options: ShadowRootOptions) -> Self {
+        self.element.shadow_root_options(options);
+        self
+    }
+
+    fn adopt_stylesheet(mut self, css: &str) -> Self {
+        self.element.adopt_stylesheet(css);
         self
     }
 }
@@ -392,6 +558,31 @@ impl<D: Dom> Element for GenericElement<D> {
         self
     }
 
+    fn style_property<'a>(
+        mut self,
+        name: &str,
+        value: impl RefSignalOrValue<'a, Item = impl Attribute>,
+    ) -> Self {
+        self.check_style_property();
+
+        value.for_each(
+            |elem, value| elem.element.style_property(name, value),
+            |elem| {
+                let name = name.to_owned();
+                let mut element = elem.element.clone();
+
+                move |new_value| {
+                    element.style_property(&name, new_value);
+
+                    async {}
+                }
+            },
+            &mut self,
+        );
+
+        self
+    }
+
     fn effect(mut self, f: impl FnOnce(&Self::DomType) + 'static) -> Self {
         self.element.effect(f);
         self
@@ -429,6 +620,40 @@ impl<D: Dom> Element for GenericElement<D> {
         self.element.on(name, f, &mut self.events);
         self
     }
+
+    fn on_mount(mut self, mut f: impl FnMut(&Self::DomType) + 'static) -> Self {
+        let element = self.element.clone();
+        self.lifecycle.on_mount(move || {
+            if let Some(element) = element.try_dom_element() {
+                f(&element)
+            }
+        });
+        self
+    }
+
+    fn on_unmount(mut self, mut f: impl FnMut(&Self::DomType) + 'static) -> Self {
+        let element = self.element.clone();
+        self.lifecycle.on_unmount(move || {
+            if let Some(element) = element.try_dom_element() {
+                f(&element)
+            }
+        });
+        self
+    }
+
+    fn on_cleanup(mut self, f: impl FnOnce() + 'static) -> Self {
+        self.resources.push(Box::new(Cleanup(Some(f))));
+        self
+    }
+
+    fn provide_context<T: 'static>(self, value: T) -> Self {
+        self.context.provide(value);
+        self
+    }
+
+    fn use_context<T: Clone + 'static>(&self) -> ContextSignal<T> {
+        self.context.signal()
+    }
 }
 
 impl<D: Dom> Executor for GenericElement<D> {
@@ -452,6 +677,8 @@ impl<D: Dom, Mutability> From<GenericElement<D, Mutability>> for Node<D> {
             node: elem.element.into(),
             resources: elem.resources,
             events: elem.events,
+            lifecycle: elem.lifecycle,
+            context: elem.context,
         }
     }
 }
@@ -596,6 +823,55 @@ pub trait Element: Sized {
         value: impl RefSignalOrValue<'a, Item = impl Attribute>,
     ) -> Self;
 
+    /// Set an inline style property
+    ///
+    /// This sets a single property, so it can be called multiple times without
+    /// one property overwriting another. The value can either be a value or a
+    /// signal, and `Option<impl Attribute>` can be used to add/remove the
+    /// property. Custom properties, like `--my-color`, are supported.
+    ///
+    /// Don't use this along with setting the `style` attribute, as they will
+    /// overwrite each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, style::Length};
+    /// let app: Div<Dry> = div()
+    ///     .style_property("color", "red")
+    ///     .style_property("margin-top", Length::Rem(1.5));
+    /// assert_eq!(
+    ///     app.freeze().to_string(),
+    ///     r#"<div style="color: red; margin-top: 1.5rem;"></div>"#
+    /// );
+    /// ```
+    ///
+    /// With a signal:
+    ///
+    /// ```
+    /// # use html::{div, Div};
+    /// # use silkenweb::{dom::Dry, prelude::*, task::{render_now, server}};
+    /// # server::block_on(server::scope(async {
+    /// let color = Mutable::new(Some("red"));
+    /// let app: Div<Dry> = div().style_property("--my-color", Sig(color.signal()));
+    /// let app = app.freeze();
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div style="--my-color: red;"></div>"#);
+    ///
+    /// color.set(None);
+    ///
+    /// render_now().await;
+    /// assert_eq!(app.to_string(), r#"<div></div>"#);
+    /// # }))
+    /// ```
+    fn style_property<'a>(
+        self,
+        name: &str,
+        value: impl RefSignalOrValue<'a, Item = impl Attribute>,
+    ) -> Self;
+
     /// Apply an effect after the next render.
     ///
     /// Effects give you access to the underlying DOM element.
@@ -657,6 +933,54 @@ pub trait Element: Sized {
     ///
     /// [MDN Events]: https://developer.mozilla.org/en-US/docs/Web/Events
     fn on(self, name: &'static str, f: impl FnMut(JsValue) + 'static) -> Self;
+
+    /// Register a callback for when the element is mounted in the document.
+    ///
+    /// This is called each time the element, or one of its ancestors, is
+    /// mounted with [`mount`][crate::mount] or added by a signal to an element
+    /// that's already mounted. It's never called for elements that are only
+    /// rendered on the server.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use web_sys::HtmlInputElement;
+    /// # use html::{input, Input};
+    /// # use silkenweb::prelude::*;
+    /// # let input: Input =
+    /// input().on_mount(|elem: &HtmlInputElement| elem.focus().unwrap());
+    /// ```
+    fn on_mount(self, f: impl FnMut(&Self::DomType) + 'static) -> Self;
+
+    /// Register a callback for when the element is unmounted from the
+    /// document.
+    ///
+    /// This is called each time the element, or one of its ancestors, is
+    /// unmounted with
+    /// [`MountHandle::unmount`][crate::document::MountHandle::unmount]
+    /// or removed by a signal. The element is still available to `f`, but it
+    /// may already have been removed from the document.
+    fn on_unmount(self, f: impl FnMut(&Self::DomType) + 'static) -> Self;
+
+    /// Register a callback for when the element is discarded.
+    ///
+    /// `f` is called once, when the element and all its resources are dropped.
+    /// Unlike [`Element::on_unmount`], this works with any [`Dom`], including
+    /// elements that are only rendered on the server.
+    fn on_cleanup(self, f: impl FnOnce() + 'static) -> Self;
+
+    /// Provide `value` as context for this element and its descendants.
+    ///
+    /// This shadows any `T` provided by an ancestor. See [`crate::context`].
+    fn provide_context<T: 'static>(self, value: T) -> Self;
+
+    /// A signal of the innermost context value of type `T`, provided by this
+    /// element or one of its ancestors.
+    ///
+    /// Context is resolved through the element's parents, so the signal
+    /// updates as the element, or one of its ancestors, is attached. It's
+    /// `None` while no `T` is provided. See [`crate::context`].
+    fn use_context<T: Clone + 'static>(&self) -> ContextSignal<T>;
 }
 
 /// An element that can have children.
@@ -783,6 +1107,68 @@ pub trait ShadowRootParent<D: InstantiableDom = DefaultDom>: Element {
     fn attach_shadow_children<N>(self, children: impl IntoIterator<Item = N> + 'static) -> Self
     where
         N: Into<Node<D>>;
+
+    /// Attach a shadow root to `self` with `options`.
+    ///
+    /// This has no effect if there's already a shadow root, so it should be
+    /// called before any other [`ShadowRootParent`] methods. With debug
+    /// assertions, server side rendering panics if `options` would be
+    /// ignored.
+    ///
+    /// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/Element/attachShadow)
+    fn shadow_root_options(self, options: ShadowRootOptions) -> Self;
+
+    /// Add a stylesheet to the shadow root, attaching an open shadow root to
+    /// `self` if there isn't one.
+    ///
+    /// In the browser, a constructable stylesheet is added to the shadow root's
+    /// `adoptedStyleSheets`. Stylesheets are cached by their content, so each
+    /// one is only parsed once, however many elements adopt it. On the server,
+    /// it's rendered as a `<style>` element at the start of the shadow root.
+    ///
+    /// See [MDN Documentation](https://developer.mozilla.org/en-US/docs/Web/API/ShadowRoot/adoptedStyleSheets)
+    fn adopt_stylesheet(self, css: &str) -> Self;
+}
+
+/// Options for [`ShadowRootParent::shadow_root_options`].
+///
+/// The default is an open shadow root with named slot assignment that doesn't
+/// delegate focus. Use the builder methods to change them:
+///
+/// ```
+/// # use silkenweb::node::element::ShadowRootOptions;
+/// let options = ShadowRootOptions::default()
+///     .closed(true)
+///     .delegates_focus(true);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ShadowRootOptions {
+    pub(crate) closed: bool,
+    pub(crate) delegates_focus: bool,
+    pub(crate) manual_slot_assignment: bool,
+}
+
+impl ShadowRootOptions {
+    /// Use a closed shadow root, so it isn't accessible from Javascript
+    /// outside the shadow root.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    /// Delegate focus to the first focusable element in the shadow root.
+    pub fn delegates_focus(mut self, delegates_focus: bool) -> Self {
+        self.delegates_focus = delegates_focus;
+        self
+    }
+
+    /// Assign children to slots manually with `HTMLSlotElement.assign`,
+    /// rather than by their `slot` attribute.
+    pub fn manual_slot_assignment(mut self, manual_slot_assignment: bool) -> Self {
+        self.manual_slot_assignment = manual_slot_assignment;
+        self
+    }
 }
 
 impl<D> fmt::Display for GenericElement<D, Const>
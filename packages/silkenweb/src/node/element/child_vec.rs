@@ -3,22 +3,33 @@ use std::mem;
 use futures_signals::signal_vec::VecDiff;
 
 use crate::{
-    dom::{private::DomElement, Dom},
-    node::Node,
+    context::Context,
+    dom::{private::DomParent, Dom},
+    node::{lifecycle::Lifecycle, Node},
 };
 
-pub struct ChildVec<D: Dom> {
-    parent: D::Element,
+/// Reactive children of `parent`, which is either an element or a shadow root.
+pub struct ChildVec<D: Dom, P = <D as Dom>::Element> {
+    parent: P,
     children: Vec<Node<D>>,
     static_child_count: usize,
+    lifecycle: Lifecycle,
+    context: Context,
 }
 
-impl<D: Dom> ChildVec<D> {
-    pub fn new(parent: D::Element, static_child_count: usize) -> Self {
+impl<D: Dom, P: DomParent<Node = D::Node>> ChildVec<D, P> {
+    pub fn new(
+        parent: P,
+        static_child_count: usize,
+        lifecycle: Lifecycle,
+        context: Context,
+    ) -> Self {
         Self {
             parent,
             children: Vec::new(),
             static_child_count,
+            lifecycle,
+            context,
         }
     }
 
@@ -27,9 +38,7 @@ impl<D: Dom> ChildVec<D> {
             VecDiff::Replace { values } => self.replace(values),
             VecDiff::InsertAt { index, value } => self.insert(index, value),
             VecDiff::UpdateAt { index, value } => self.set_at(index, value),
-            VecDiff::RemoveAt { index } => {
-                self.remove(index);
-            }
+            VecDiff::RemoveAt { index } => self.remove(index),
             VecDiff::Move {
                 old_index,
                 new_index,
@@ -52,29 +61,25 @@ impl<D: Dom> ChildVec<D> {
         for child in &self.children {
             parent.append_child(child.as_node());
         }
+
+        for child in &self.children {
+            self.context.add_child(&child.context);
+            self.lifecycle.add_child(&child.lifecycle);
+        }
     }
 
     fn insert(&mut self, index: usize, new_child: impl Into<Node<D>>) {
         let new_child = new_child.into();
-
-        if index >= self.children.len() {
-            self.push(new_child);
-            return;
-        }
-
-        assert!(index < self.children.len());
-
-        self.parent.insert_child_before(
-            index + self.static_child_count,
-            new_child.as_node(),
-            Some(self.children[index].as_node()),
-        );
-
-        self.children.insert(index, new_child);
+        let lifecycle = new_child.lifecycle.clone();
+        self.context.add_child(&new_child.context);
+        self.attach(index, new_child);
+        self.lifecycle.add_child(&lifecycle);
     }
 
     fn set_at(&mut self, index: usize, new_child: impl Into<Node<D>>) {
         let new_child = new_child.into();
+        let lifecycle = new_child.lifecycle.clone();
+        self.context.add_child(&new_child.context);
         let old_child = &mut self.children[index];
 
         self.parent.replace_child(
@@ -83,26 +88,28 @@ impl<D: Dom> ChildVec<D> {
             old_child.as_node(),
         );
 
-        *old_child = new_child;
+        let old_child = mem::replace(old_child, new_child);
+        self.lifecycle.remove_child(&old_child.lifecycle);
+        self.lifecycle.add_child(&lifecycle);
     }
 
-    fn remove(&mut self, index: usize) -> Node<D> {
-        let old_child = self.children.remove(index);
-        self.parent
-            .remove_child(index + self.static_child_count, old_child.as_node());
-
-        old_child
+    fn remove(&mut self, index: usize) {
+        let old_child = self.detach(index);
+        self.lifecycle.remove_child(&old_child.lifecycle);
     }
 
+    /// Move a child, without unmounting it.
     fn relocate(&mut self, old_index: usize, new_index: usize) {
-        let child = self.remove(old_index);
-        self.insert(new_index, child);
+        let child = self.detach(old_index);
+        self.attach(new_index, child);
     }
 
     fn push(&mut self, new_child: impl Into<Node<D>>) {
         let new_child = new_child.into();
-        self.parent.append_child(new_child.as_node());
-        self.children.push(new_child);
+        let lifecycle = new_child.lifecycle.clone();
+        self.context.add_child(&new_child.context);
+        self.attach(self.children.len(), new_child);
+        self.lifecycle.add_child(&lifecycle);
     }
 
     fn pop(&mut self) {
@@ -113,20 +120,51 @@ impl<D: Dom> ChildVec<D> {
                 self.children.len() + self.static_child_count,
                 removed_child.as_node(),
             );
+            self.lifecycle.remove_child(&removed_child.lifecycle);
         }
     }
 
     fn clear(&mut self) {
+        let children = mem::take(&mut self.children);
+
         if self.static_child_count > 0 {
             let mut parent = self.parent.clone();
-            let children = mem::take(&mut self.children);
 
-            for (index, child) in children.into_iter().enumerate().rev() {
+            for (index, child) in children.iter().enumerate().rev() {
                 parent.remove_child(index + self.static_child_count, child.as_node());
             }
         } else {
-            self.children.clear();
             self.parent.clear_children();
         }
+
+        for child in &children {
+            self.lifecycle.remove_child(&child.lifecycle);
+        }
+    }
+
+    /// Add a child to the DOM and `children`, without mounting it.
+    fn attach(&mut self, index: usize, new_child: Node<D>) {
+        if index >= self.children.len() {
+            self.parent.append_child(new_child.as_node());
+            self.children.push(new_child);
+            return;
+        }
+
+        self.parent.insert_child_before(
+            index + self.static_child_count,
+            new_child.as_node(),
+            Some(self.children[index].as_node()),
+        );
+
+        self.children.insert(index, new_child);
+    }
+
+    /// Remove a child from the DOM and `children`, without unmounting it.
+    fn detach(&mut self, index: usize) -> Node<D> {
+        let old_child = self.children.remove(index);
+        self.parent
+            .remove_child(index + self.static_child_count, old_child.as_node());
+
+        old_child
     }
 }
@@ -0,0 +1,148 @@
+use std::{cell::RefCell, mem, rc::Rc};
+
+/// Mount and unmount callbacks for an element and its descendants.
+///
+/// This is only allocated if the element, or one of its descendants, has
+/// callbacks or reactive children.
+#[derive(Clone, Default)]
+pub(crate) struct Lifecycle(Option<Rc<RefCell<State>>>);
+
+type Callbacks = Vec<Box<dyn FnMut()>>;
+
+#[derive(Default)]
+struct State {
+    mounted: bool,
+    on_mount: Callbacks,
+    on_unmount: Callbacks,
+    children: Vec<Rc<RefCell<State>>>,
+}
+
+impl Lifecycle {
+    pub fn on_mount(&mut self, f: impl FnMut() + 'static) {
+        self.state().borrow_mut().on_mount.push(Box::new(f));
+    }
+
+    pub fn on_unmount(&mut self, f: impl FnMut() + 'static) {
+        self.state().borrow_mut().on_unmount.push(Box::new(f));
+    }
+
+    /// Get a shared reference to this lifecycle, so children can be added
+    /// after the element is built.
+    pub fn shared(&mut self) -> Self {
+        self.state();
+        self.clone()
+    }
+
+    /// Combine the lifecycle of a static child with this one.
+    pub fn combine(&mut self, child: Self) {
+        if let Some(child) = child.0 {
+            self.state().borrow_mut().children.push(child);
+        }
+    }
+
+    /// Add a child after the element is built, mounting it if we're mounted.
+    pub fn add_child(&self, child: &Self) {
+        if let (Some(state), Some(child_state)) = (&self.0, &child.0) {
+            let mounted = {
+                let mut state = state.borrow_mut();
+                state.children.push(child_state.clone());
+                state.mounted
+            };
+
+            if mounted {
+                child.mount();
+            }
+        }
+    }
+
+    /// Remove a child after the element is built, unmounting it if we're
+    /// mounted.
+    pub fn remove_child(&self, child: &Self) {
+        if let (Some(state), Some(child_state)) = (&self.0, &child.0) {
+            let mounted = {
+                let mut state = state.borrow_mut();
+                state
+                    .children
+                    .retain(|existing| !Rc::ptr_eq(existing, child_state));
+                state.mounted
+            };
+
+            if mounted {
+                child.unmount();
+            }
+        }
+    }
+
+    /// Mount children, then run our mount callbacks.
+    pub fn mount(&self) {
+        if let Some(state) = &self.0 {
+            let children = {
+                let mut state = state.borrow_mut();
+
+                if state.mounted {
+                    return;
+                }
+
+                state.mounted = true;
+                state.children.clone()
+            };
+
+            for child in children {
+                Self(Some(child)).mount();
+            }
+
+            Self::run(state, |state| &mut state.on_mount);
+        }
+    }
+
+    /// Run our unmount callbacks, then unmount children.
+    pub fn unmount(&self) {
+        if let Some(state) = &self.0 {
+            {
+                let mut state = state.borrow_mut();
+
+                if !state.mounted {
+                    return;
+                }
+
+                state.mounted = false;
+            }
+
+            Self::run(state, |state| &mut state.on_unmount);
+            let children = state.borrow().children.clone();
+
+            for child in children {
+                Self(Some(child)).unmount();
+            }
+        }
+    }
+
+    fn state(&mut self) -> &Rc<RefCell<State>> {
+        self.0.get_or_insert_with(Rc::default)
+    }
+
+    /// Run some callbacks, without holding a borrow on `state`, so they can add
+    /// more callbacks.
+    fn run(state: &RefCell<State>, callbacks: impl Fn(&mut State) -> &mut Callbacks) {
+        let mut running = mem::take(callbacks(&mut state.borrow_mut()));
+
+        for f in &mut running {
+            f();
+        }
+
+        let mut state = state.borrow_mut();
+        let added = mem::replace(callbacks(&mut state), running);
+        callbacks(&mut state).extend(added);
+    }
+}
+
+/// Run a function when dropped.
+pub(crate) struct Cleanup<F: FnOnce()>(pub Option<F>);
+
+impl<F: FnOnce()> Drop for Cleanup<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Typed values for [`Element::style_property`], and scoping for stylesheets.
+//!
+//! [`Element::style_property`]: crate::node::element::Element::style_property
+use std::{borrow::Cow, cell::RefCell};
+
+use silkenweb_signals_ext::value::{RefValue, Value};
+
+use crate::attribute::{AsAttribute, Attribute};
+
+/// A CSS length or percentage.
+///
+/// See [MDN CSS length](https://developer.mozilla.org/en-US/docs/Web/CSS/length)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Length {
+    Px(f64),
+    Em(f64),
+    Rem(f64),
+    Ch(f64),
+    Ex(f64),
+    Vw(f64),
+    Vh(f64),
+    Vmin(f64),
+    Vmax(f64),
+    Cm(f64),
+    Mm(f64),
+    In(f64),
+    Pt(f64),
+    Pc(f64),
+    Percent(f64),
+}
+
+impl Attribute for Length {
+    fn text(&self) -> Option<Cow<str>> {
+        let (length, units) = match self {
+            Length::Px(l) => (l, "px"),
+            Length::Em(l) => (l, "em"),
+            Length::Rem(l) => (l, "rem"),
+            Length::Ch(l) => (l, "ch"),
+            Length::Ex(l) => (l, "ex"),
+            Length::Vw(l) => (l, "vw"),
+            Length::Vh(l) => (l, "vh"),
+            Length::Vmin(l) => (l, "vmin"),
+            Length::Vmax(l) => (l, "vmax"),
+            Length::Cm(l) => (l, "cm"),
+            Length::Mm(l) => (l, "mm"),
+            Length::In(l) => (l, "in"),
+            Length::Pt(l) => (l, "pt"),
+            Length::Pc(l) => (l, "pc"),
+            Length::Percent(l) => (l, "%"),
+        };
+
+        Some(format!("{length}{units}").into())
+    }
+}
+
+impl AsAttribute<Length> for Length {}
+
+impl Value for Length {}
+
+/// A CSS time.
+///
+/// See [MDN CSS time](https://developer.mozilla.org/en-US/docs/Web/CSS/time)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Time {
+    S(f64),
+    Ms(f64),
+}
+
+impl Attribute for Time {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(
+            match self {
+                Time::S(t) => format!("{t}s"),
+                Time::Ms(t) => format!("{t}ms"),
+            }
+            .into(),
+        )
+    }
+}
+
+impl AsAttribute<Time> for Time {}
+
+impl Value for Time {}
+
+/// A reference to a CSS custom property, with an optional fallback.
+///
+/// This is useful for setting a property to the value of a custom property
+/// from [`css!`]'s `var` module.
+///
+/// ```
+/// # use html::{div, Div};
+/// # use silkenweb::{dom::Dry, prelude::*, style::Var};
+/// let app: Div<Dry> = div().style_property("color", Var::new("--brand-color"));
+/// assert_eq!(
+///     app.freeze().to_string(),
+///     r#"<div style="color: var(--brand-color);"></div>"#
+/// );
+/// ```
+///
+/// [`css!`]: crate::css
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Var<'a> {
+    name: &'a str,
+    fallback: Option<&'a str>,
+}
+
+impl<'a> Var<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            fallback: None,
+        }
+    }
+
+    pub fn with_fallback(name: &'a str, fallback: &'a str) -> Self {
+        Self {
+            name,
+            fallback: Some(fallback),
+        }
+    }
+}
+
+impl<'a> Attribute for Var<'a> {
+    fn text(&self) -> Option<Cow<str>> {
+        Some(
+            match self.fallback {
+                Some(fallback) => format!("var({}, {fallback})", self.name),
+                None => format!("var({})", self.name),
+            }
+            .into(),
+        )
+    }
+}
+
+impl<'a> AsAttribute<Var<'a>> for Var<'a> {}
+
+impl<'a> RefValue<'a> for Var<'a> {}
+
+/// Call `f`, marking any elements it creates with the `scope` attribute.
+///
+/// [`css!`] generates `stylesheet::scoped` for `scoped` stylesheets, which
+/// should be used in preference to this. Elements are only marked if they're
+/// created while `f` is running, so elements created later, for example in a
+/// signal's `map` closure, need to be created within their own call to
+/// `scoped`. Scopes nest, with elements marked by the innermost scope only.
+///
+/// ```
+/// # use html::{div, Div};
+/// # use silkenweb::{dom::Dry, prelude::*, style};
+/// let app: Div<Dry> = style::scoped("data-my-scope", div);
+/// assert_eq!(app.freeze().to_string(), r#"<div data-my-scope=""></div>"#);
+/// ```
+///
+/// [`css!`]: crate::css
+pub fn scoped<T>(scope: &'static str, f: impl FnOnce() -> T) -> T {
+    SCOPES.with(|scopes| scopes.borrow_mut().push(scope));
+    let _pop_scope = PopScope;
+    f()
+}
+
+/// Leave the current scope on drop, so the scope is left even if `f` panics.
+struct PopScope;
+
+impl Drop for PopScope {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+pub(crate) fn current_scope() -> Option<&'static str> {
+    SCOPES.with(|scopes| scopes.borrow().last().copied())
+}
+
+thread_local! {
+    static SCOPES: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
@@ -1,18 +1,26 @@
+use futures_signals::{
+    signal::{Mutable, SignalExt},
+    signal_vec::MutableVec,
+};
+use silkenweb::{
+    dom::Dry,
+    node::{
+        element::{Const, GenericElement},
+        Component,
+    },
+    prelude::{html::span, ParentElement},
+    task::render_now,
+};
+#[cfg(feature = "declarative-shadow-dom")]
+use silkenweb::{
+    elements::html::{div, slot},
+    node::element::ShadowRootOptions,
+    task::server,
+};
+
 #[cfg(feature = "declarative-shadow-dom")]
 #[test]
 fn component() {
-    use silkenweb::{
-        dom::Dry,
-        node::{
-            element::{Const, GenericElement},
-            Component,
-        },
-        prelude::{
-            html::{div, span},
-            ParentElement,
-        },
-    };
-
     let mut comp = Component::<Dry>::styled(r#"* { color: red }"#);
     let slot_0 = comp.slot(span().text("slot 0"));
     let slot_1 = comp.slot(span().text("slot 1"));
@@ -26,3 +34,74 @@ fn component() {
         r#"<div><template shadowroot="open"><style>* { color: red }</style><div><div><slot name="0"></slot></div><div><slot name="1"></slot></div></div></template><span slot="0">slot 0</span><span slot="1">slot 1</span></div>"#
     );
 }
+
+isomorphic_test! {
+    async fn component_slot_signals() {
+        let text = Mutable::new("first");
+        let items = MutableVec::new_with_values(vec!["a"]);
+        let mut comp = Component::<Dry>::new();
+        let slot_0 = comp.slot_signal(text.signal().map(|text| span().text(text)));
+        let slot_1 = comp.multi_slot_signal(items.signal_vec().map(|item| span().text(item)));
+
+        let comp: GenericElement<Dry, Const> = comp
+            .child(slot_0)
+            .child(slot_1)
+            .child_signal(text.signal().map(|text| span().text(text)))
+            .into();
+        render_now().await;
+
+        assert!(comp
+            .to_string()
+            .ends_with(r#"<span slot="0">first</span><span slot="1">a</span></div>"#));
+
+        text.set("second");
+        items.lock_mut().push("b");
+        render_now().await;
+
+        assert!(comp.to_string().ends_with(
+            r#"<span slot="0">second</span><span slot="1">a</span><span slot="1">b</span></div>"#
+        ));
+    }
+}
+
+#[cfg(feature = "declarative-shadow-dom")]
+#[test]
+fn component_shadow_child_signal() {
+    server::block_on(server::scope(async {
+        let text = Mutable::new("first");
+        let items = MutableVec::new_with_values(vec!["a"]);
+        let comp: GenericElement<Dry, Const> = Component::<Dry>::new()
+            .child(slot())
+            .child_signal(text.signal().map(|text| span().text(text)))
+            .children_signal(items.signal_vec().map(|item| span().text(item)))
+            .child(slot().name("last"))
+            .into();
+        render_now().await;
+        text.set("second");
+        items.lock_mut().insert(0, "b");
+        render_now().await;
+
+        assert_eq!(
+            comp.to_string(),
+            r#"<div><template shadowroot="open"><slot></slot><span>second</span><span>b</span><span>a</span><slot name="last"></slot></template></div>"#
+        );
+    }));
+}
+
+#[cfg(feature = "declarative-shadow-dom")]
+#[test]
+fn component_shadow_root_options() {
+    let comp: GenericElement<Dry, Const> = Component::<Dry>::with_options(
+        ShadowRootOptions::default()
+            .closed(true)
+            .delegates_focus(true),
+    )
+    .adopt_stylesheet("slot { color: red }")
+    .child(slot())
+    .into();
+
+    assert_eq!(
+        comp.to_string(),
+        r#"<div><template shadowroot="closed" shadowrootdelegatesfocus><style>slot { color: red }</style><slot></slot></template></div>"#
+    );
+}
@@ -0,0 +1,117 @@
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    dom::{Dom, Dry, Hydro, Template},
+    elements::html::{div, p, Div, P},
+    node::{
+        element::{Const, Element, ParentElement},
+        Node,
+    },
+    task::render_now,
+    value::Sig,
+};
+use silkenweb_macros::cfg_browser;
+
+#[derive(Clone)]
+struct Theme(&'static str);
+
+fn themed<D: Dom>(elem: P<D>, text: &'static str) -> P<D> {
+    let theme = elem.use_context::<Theme>();
+
+    elem.text(Sig(theme.map(move |theme| {
+        format!("{text}: {}", theme.map_or("none", |theme| theme.0))
+    })))
+}
+
+fn themed_text<D: Dom>(text: &'static str) -> P<D> {
+    themed(p(), text)
+}
+
+/// `inner` is built before it has a parent, and `shadowed` provides its own
+/// theme.
+fn app<D: Dom>() -> Div<D> {
+    let inner = div().child(themed_text("inner"));
+
+    div()
+        .provide_context(Theme("dark"))
+        .child(themed_text("outer"))
+        .child(inner)
+        .child(
+            div()
+                .provide_context(Theme("light"))
+                .child(themed_text("shadowed")),
+        )
+}
+
+const APP_HTML: &str = "<div><p>outer: dark</p><div><p>inner: dark</p></div><div><p>shadowed: \
+                        light</p></div></div>";
+
+async fn render<D: Dom>(app: Div<D>) -> String {
+    let app = app.freeze();
+    render_now().await;
+    app.to_string()
+}
+
+isomorphic_test! {
+    async fn context_dry() {
+        assert_eq!(render(app::<Dry>()).await, APP_HTML);
+    }
+}
+
+isomorphic_test! {
+    async fn context_hydro() {
+        assert_eq!(render(app::<Hydro>()).await, APP_HTML);
+    }
+}
+
+#[cfg_browser(true)]
+#[wasm_bindgen_test::wasm_bindgen_test]
+async fn context_wet() {
+    assert_eq!(render(app::<silkenweb::dom::Wet>()).await, APP_HTML);
+}
+
+isomorphic_test! {
+    async fn context_template() {
+        let template: Div<Template<()>, Const> = app().freeze();
+        render_now().await;
+        let node: Node = template.instantiate(&()).into();
+        assert_eq!(node.to_string(), APP_HTML);
+    }
+}
+
+isomorphic_test! {
+    async fn context_template_instance() {
+        let template: P<Template<&'static str, Dry>, Const> =
+            p().on_instantiate(|p, text| themed(p, *text)).freeze();
+        let app: Div<Dry> = div()
+            .provide_context(Theme("dark"))
+            .child(template.instantiate(&"instance"));
+
+        assert_eq!(render(app).await, "<div><p>instance: dark</p></div>");
+    }
+}
+
+isomorphic_test! {
+    async fn context_without_provider() {
+        assert_eq!(
+            render(div::<Dry>().child(themed_text("text"))).await,
+            "<div><p>text: none</p></div>"
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn context_signal_children() {
+        let text = Mutable::new("first");
+        let app: Div<Dry> = div()
+            .provide_context(Theme("dark"))
+            .child(Sig(text.signal().map(themed_text)));
+        let app: Div<Dry, Const> = app.freeze();
+
+        render_now().await;
+        assert_eq!(app.to_string(), "<div><p>first: dark</p></div>");
+
+        text.set("second");
+        render_now().await;
+        assert_eq!(app.to_string(), "<div><p>second: dark</p></div>");
+    }
+}
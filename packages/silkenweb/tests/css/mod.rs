@@ -35,3 +35,252 @@ isomorphic_test! {
         );
     }
 }
+
+mod composes {
+    use silkenweb::{document::Document, dom::DefaultDom};
+
+    silkenweb::css!(
+        content = r#"
+            .base { color: red }
+            .local-composition { composes: base; }
+            .global-composition { composes: external from global; }
+            .file-composition { composes: composed from "tests/css/composed.css"; }
+        "#,
+        transpile = (modules)
+    );
+
+    fn classes(class_list: &str) -> Vec<&str> {
+        class_list.split(' ').collect()
+    }
+
+    isomorphic_test! {
+        async fn css_composes() {
+            assert_eq!(classes(class::BASE).len(), 1);
+            assert_eq!(classes(class::LOCAL_COMPOSITION)[1], class::BASE);
+            assert_eq!(classes(class::GLOBAL_COMPOSITION)[1], "external");
+
+            let file_composition = classes(class::FILE_COMPOSITION);
+            assert_eq!(file_composition.len(), 2);
+            assert!(file_composition[1].ends_with("_composed"));
+
+            DefaultDom::unmount_all();
+            stylesheet::mount();
+            let composed_id = concat!(
+                "silkenweb-style:",
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/css/composed.css"
+            );
+            assert!(DefaultDom::head_inner_html().contains(composed_id));
+        }
+    }
+}
+
+mod exports {
+    silkenweb::css!(
+        content = r#"
+            .spinner {
+                --spinner-color: red;
+                color: var(--spinner-color);
+                animation: spin 1s;
+            }
+
+            @keyframes spin {
+                to { transform: rotate(360deg); }
+            }
+        "#,
+        export_vars,
+        export_animations,
+        transpile = (modules, dashed_idents)
+    );
+
+    isomorphic_test! {
+        async fn css_exports() {
+            assert!(var::SPINNER_COLOR.starts_with("--"));
+            assert!(var::SPINNER_COLOR.ends_with("_spinner-color"));
+            assert!(animation::SPIN.ends_with("_spin"));
+            assert!(!class::SPINNER.contains(' '));
+        }
+    }
+}
+
+mod shared_names {
+    silkenweb::css!(
+        content = r#"
+            .spin {
+                animation: spin 1s;
+            }
+
+            @keyframes spin {
+                to { transform: rotate(360deg); }
+            }
+        "#,
+        export_animations,
+        transpile = (modules)
+    );
+
+    isomorphic_test! {
+        async fn class_with_keyframes_name() {
+            assert!(class::SPIN.ends_with("_spin"));
+            assert!(animation::SPIN.ends_with("_spin"));
+        }
+    }
+}
+
+mod user_modules {
+    silkenweb::css!(
+        content = r#"
+            .spinner {
+                --spinner-color: red;
+                animation: spin 1s;
+            }
+
+            @keyframes spin {
+                to { transform: rotate(360deg); }
+            }
+        "#
+    );
+
+    // `var` and `animation` are only generated when asked for.
+    mod var {}
+    mod animation {}
+
+    isomorphic_test! {
+        async fn no_var_or_animation_modules() {
+            assert_eq!(class::SPINNER, "spinner");
+        }
+    }
+}
+
+mod scoped {
+    use silkenweb::{
+        dom::DefaultDom,
+        elements::html::{div, p, Div},
+        node::element::{Const, Element, ParentElement},
+        task::render_now,
+    };
+    use silkenweb_macros::cfg_browser;
+
+    silkenweb::css!(
+        content = ".message, p:hover::before { color: red; }",
+        scoped
+    );
+
+    isomorphic_test! {
+        async fn css_scoped() {
+            let unscoped = p();
+            let elem: Div<DefaultDom, Const> =
+                stylesheet::scoped(|| div().class("message").child(unscoped)).freeze();
+            render_now().await;
+
+            assert_eq!(
+                elem.to_string(),
+                format!(r#"<div {}="" class="message"><p></p></div>"#, stylesheet::SCOPE)
+            );
+            assert_eq!(
+                stylesheet::text(),
+                format!(
+                    ".message[{0}], p:hover[{0}]::before {{ color: red; }}",
+                    stylesheet::SCOPE
+                )
+            );
+        }
+    }
+
+    isomorphic_test! {
+        async fn stable_scope() {
+            // The scope is a hash of the stylesheet, which shouldn't change between builds.
+            assert_eq!(stylesheet::SCOPE, "data-silkenweb-48621d5");
+        }
+    }
+
+    #[cfg_browser(false)]
+    #[test]
+    fn scope_left_on_panic() {
+        let result = std::panic::catch_unwind(|| {
+            silkenweb::style::scoped("data-panic-scope", || panic!("Panic in scope"))
+        });
+        assert!(result.is_err());
+
+        let elem: Div<silkenweb::dom::Dry> = div();
+        assert_eq!(elem.freeze().to_string(), "<div></div>");
+    }
+
+    mod root {
+        silkenweb::css!(
+            content = ":root { --color: red; } html.dark, .message { color: var(--color); }",
+            scoped
+        );
+
+        isomorphic_test! {
+            async fn root_not_scoped() {
+                assert_eq!(
+                    stylesheet::text(),
+                    format!(
+                        ":root {{ --color: red; }} html.dark, .message[{}] {{ color: var(--color); }}",
+                        stylesheet::SCOPE
+                    )
+                );
+            }
+        }
+    }
+}
+
+mod styled {
+    use futures_signals::signal::Mutable;
+    use silkenweb::{
+        document::Document,
+        dom::DefaultDom,
+        elements::html::{div, Div},
+        node::element::Const,
+        task::render_now,
+        value::Sig,
+    };
+
+    isomorphic_test! {
+        async fn styled_element() {
+            let color = Mutable::new("red");
+            let elem: Div<DefaultDom, Const> = silkenweb::styled!(div(), {
+                color: ${Sig(color.signal())};
+                margin-top: 1px;
+            })
+            .freeze();
+            render_now().await;
+
+            let html = elem.to_string();
+            assert!(html.starts_with(r#"<div class="silkenweb-styled-"#));
+            assert!(html.ends_with(r#"-0: red;"></div>"#));
+            assert!(DefaultDom::head_inner_html().contains("margin-top:1px"));
+
+            color.set("blue");
+            render_now().await;
+            assert!(elem.to_string().ends_with(r#"-0: blue;"></div>"#));
+        }
+    }
+}
+
+mod sass {
+    silkenweb::css!("tests/css/sass.scss");
+
+    isomorphic_test! {
+        async fn css_sass() {
+            assert_eq!(class::BRAND, "brand");
+            assert_eq!(class::BRAND_STRONG, "brand-strong");
+            assert!(stylesheet::text().contains("color: hotpink;"));
+        }
+    }
+}
+
+mod source_map {
+    silkenweb::css!(
+        content = ".mapped { color: red }",
+        transpile = (pretty, source_map)
+    );
+
+    isomorphic_test! {
+        async fn css_source_map() {
+            assert_eq!(class::MAPPED, "mapped");
+            assert!(stylesheet::text()
+                .contains("/*# sourceMappingURL=data:application/json;"));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use futures_signals::signal::SignalExt;
+use silkenweb::{
+    custom_element::{self, CustomElement, Host},
+    dom::Wet,
+    elements::html::p,
+    node::{element::ParentElement, Node},
+    task::render_now,
+    value::Sig,
+};
+use silkenweb_base::document;
+use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+struct Greeting;
+
+impl CustomElement for Greeting {
+    const NAME: &'static str = "silkenweb-test-greeting";
+    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &["name"];
+    const PROPERTIES: &'static [&'static str] = &["punctuation"];
+
+    fn render(host: &Host) -> Node<Wet> {
+        let name = host
+            .attribute("name")
+            .map(|name| name.unwrap_or_else(|| "world".to_string()));
+        let punctuation = host
+            .property("punctuation")
+            .map(|punctuation| punctuation.as_string().unwrap_or_default());
+
+        p().text("Hello, ")
+            .text(Sig(name))
+            .text(Sig(punctuation))
+            .into()
+    }
+}
+
+#[wasm_bindgen_test]
+async fn custom_element() {
+    custom_element::define::<Greeting>();
+
+    let element = document::create_element(Greeting::NAME);
+    let shadow_html = || element.shadow_root().unwrap_throw().inner_html();
+    let body = document::body().unwrap_throw();
+    body.append_child(&element).unwrap_throw();
+    render_now().await;
+    assert_eq!(shadow_html(), "<p>Hello, world</p>");
+
+    element.set_attribute("name", "Rustacean").unwrap_throw();
+    js_sys::Reflect::set(&element, &"punctuation".into(), &"!".into()).unwrap_throw();
+    render_now().await;
+    assert_eq!(shadow_html(), "<p>Hello, Rustacean!</p>");
+
+    element.remove();
+    assert_eq!(shadow_html(), "");
+
+    // Attributes and properties are kept while the element is disconnected.
+    body.append_child(&element).unwrap_throw();
+    render_now().await;
+    assert_eq!(shadow_html(), "<p>Hello, Rustacean!</p>");
+    element.remove();
+}
@@ -1,11 +1,17 @@
 use futures_signals::{
-    signal::Mutable,
+    signal::{Mutable, SignalExt},
     signal_vec::{MutableVec, MutableVecLockMut, SignalVecExt},
 };
 use silkenweb::{
     dom::DefaultDom,
     elements::{
         html::{div, p, Div},
+        mathml::{math, mfrac, mi, mn, mo},
+        svg::{
+            attributes::{Core, Presentation},
+            content_type::{Paint, Points, Transform, ViewBox},
+            linear_gradient, polygon, rect, svg,
+        },
         HtmlElement,
     },
     node::{element::Element, text, Node},
@@ -64,6 +70,20 @@ render_test!(
     div().children([p().text("Hello"), p().text("World!")]),
     "<div><p>Hello</p><p>World!</p></div>"
 );
+render_test!(
+    mathml,
+    div().child(
+        math()
+            .display("block")
+            .child(mfrac().child(mi().text("x")).child(mn().text("2")))
+    ),
+    r#"<div><math display="block"><mfrac><mi>x</mi><mn>2</mn></mfrac></math></div>"#
+);
+render_test!(
+    mathml_boolean,
+    mo().stretchy(false).text("("),
+    r#"<mo stretchy="false">(</mo>"#
+);
 
 // Make sure the test is actually run
 #[cfg_browser(false)]
@@ -241,6 +261,48 @@ isomorphic_test! {
     }
 }
 
+render_test!(
+    svg_typed_attributes,
+    svg()
+        .view_box(ViewBox::new(0.0, 0.0, 100.0, 50.0))
+        .child(linear_gradient().id("gradient"))
+        .child(
+            rect()
+                .fill(Paint::Url("gradient".to_string()))
+                .stroke(Paint::None)
+        )
+        .child(polygon().points(Points::new().point(0.0, 0.0).point(10.5, 20.0))),
+    concat!(
+        r#"<svg viewBox="0 0 100 50"><linearGradient id="gradient"></linearGradient>"#,
+        r#"<rect fill="url(#gradient)" stroke="none"></rect>"#,
+        r#"<polygon points="0,0 10.5,20"></polygon></svg>"#
+    )
+);
+
+isomorphic_test! {
+    async fn transform_signal() {
+        let angle = Mutable::new(0.0);
+        let elem: Node = rect()
+            .transform(Sig(angle.signal().map(|angle| {
+                Transform::new().translate(10.0, 20.0).rotate_about(angle, 5.0, 5.0)
+            })))
+            .into();
+
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<rect transform="translate(10 20) rotate(0 5 5)"></rect>"#
+        );
+
+        angle.set(45.0);
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<rect transform="translate(10 20) rotate(45 5 5)"></rect>"#
+        );
+    }
+}
+
 isomorphic_test! {
     async fn classes_signal() {
         let test_class1 = Mutable::new(Some("test-class-1"));
@@ -293,6 +355,67 @@ isomorphic_test! {
     }
 }
 
+render_test!(
+    style_properties,
+    div()
+        .style_property("color", "red")
+        .style_property("margin-top", "1.5rem"),
+    r#"<div style="color: red; margin-top: 1.5rem;"></div>"#
+);
+
+render_test!(
+    style_property_with_separators,
+    div()
+        .style_property("--icon", "url(data:image/png;base64,AAAA)")
+        .style_property("--icon", "url(data:image/gif;base64,BBBB)")
+        .style_property("color", "red"),
+    r#"<div style="--icon: url(data:image/gif;base64,BBBB); color: red;"></div>"#
+);
+
+#[cfg(debug_assertions)]
+#[cfg_browser(false)]
+#[test]
+#[should_panic]
+fn style_attribute_then_property() {
+    let _ = div()
+        .attribute("style", "color: red;")
+        .style_property("margin-top", "1rem");
+}
+
+#[cfg(debug_assertions)]
+#[cfg_browser(false)]
+#[test]
+#[should_panic]
+fn style_property_then_attribute() {
+    let _ = div()
+        .style_property("margin-top", "1rem")
+        .attribute("style", "color: red;");
+}
+
+isomorphic_test! {
+    async fn style_property_signal() {
+        let color = Mutable::new(Some("red"));
+        let elem: Node = div()
+            .style_property("margin-top", "1.5rem")
+            .style_property("color", Sig(color.signal()))
+            .into();
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<div style="margin-top: 1.5rem; color: red;"></div>"#
+        );
+        color.set(Some("blue"));
+        render_now().await;
+        assert_eq!(
+            elem.to_string(),
+            r#"<div style="margin-top: 1.5rem; color: blue;"></div>"#
+        );
+        color.set(None);
+        render_now().await;
+        assert_eq!(elem.to_string(), r#"<div style="margin-top: 1.5rem;"></div>"#);
+    }
+}
+
 isomorphic_test! {
     async fn text_node() {
         let elem: Node = div().child(text("Hello, world!")).into();
@@ -0,0 +1,233 @@
+use std::num::ParseIntError;
+
+use futures_signals::{
+    signal::Mutable,
+    signal_vec::{MutableVec, SignalVecExt},
+};
+use silkenweb::{
+    dom::Dry,
+    elements::html::{div, input, option, select, textarea, Input, Select, Textarea},
+    hydration::hydrate,
+    mount,
+    node::element::ParentElement,
+    prelude::HtmlElement,
+    task::render_now,
+};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::{create_app_container, query_element, APP_ID};
+
+const INPUT_ID: &str = "input";
+
+isomorphic_test! {
+    async fn bind_value_render() {
+        let text = Mutable::new("Hello".to_string());
+        let input: Input<Dry> = input().bind_value(&text);
+        let textarea: Textarea<Dry> = textarea().bind_value(&text);
+        let input = input.freeze();
+        let textarea = textarea.freeze();
+
+        render_now().await;
+        assert_eq!(input.to_string(), r#"<input value="Hello">"#);
+        assert_eq!(textarea.to_string(), "<textarea>Hello</textarea>");
+
+        text.set("Goodbye".to_string());
+        render_now().await;
+        assert_eq!(input.to_string(), r#"<input value="Goodbye">"#);
+        assert_eq!(textarea.to_string(), "<textarea>Goodbye</textarea>");
+    }
+}
+
+#[wasm_bindgen_test]
+async fn bind_value() {
+    create_app_container(APP_ID).await;
+
+    let text = Mutable::new("Hello".to_string());
+    mount(APP_ID, input().id(INPUT_ID).bind_value(&text));
+    render_now().await;
+    assert_eq!(dom_input().value(), "Hello");
+
+    text.set("Goodbye".to_string());
+    render_now().await;
+    assert_eq!(dom_input().value(), "Goodbye");
+
+    type_text("Typed");
+    assert_eq!(text.get_cloned(), "Typed");
+}
+
+#[wasm_bindgen_test]
+async fn bind_parsed() {
+    create_app_container(APP_ID).await;
+
+    let number = Mutable::new(1);
+    let error: Mutable<Option<ParseIntError>> = Mutable::new(None);
+    mount(APP_ID, input().id(INPUT_ID).bind_parsed(&number, &error));
+    render_now().await;
+    assert_eq!(dom_input().value(), "1");
+
+    type_text("not a number");
+    assert_eq!(number.get(), 1);
+    assert!(error.lock_ref().is_some());
+
+    type_text(" 42 ");
+    assert_eq!(number.get(), 42);
+    assert!(error.lock_ref().is_none());
+
+    render_now().await;
+    assert_eq!(dom_input().value(), " 42 ", "Equivalent text is kept");
+}
+
+#[wasm_bindgen_test]
+async fn bind_checked() {
+    create_app_container(APP_ID).await;
+
+    let checked = Mutable::new(false);
+    mount(
+        APP_ID,
+        input()
+            .id(INPUT_ID)
+            .r#type("checkbox")
+            .bind_checked(&checked),
+    );
+    render_now().await;
+    assert!(!dom_input().checked());
+
+    checked.set(true);
+    render_now().await;
+    assert!(dom_input().checked());
+
+    dom_input().click();
+    assert!(!checked.get());
+}
+
+isomorphic_test! {
+    async fn bind_checked_render() {
+        let checked = Mutable::new(true);
+        let input: Input<Dry> = input().r#type("checkbox").bind_checked(&checked);
+        let input = input.freeze();
+
+        render_now().await;
+        assert_eq!(input.to_string(), r#"<input type="checkbox" checked="">"#);
+
+        checked.set(false);
+        render_now().await;
+        assert_eq!(input.to_string(), r#"<input type="checkbox">"#);
+    }
+}
+
+isomorphic_test! {
+    async fn bind_selected_render() {
+        let selected = Mutable::new(2);
+        let options = MutableVec::new_with_values(vec![2]);
+        let select: Select<Dry> = select()
+            .bind_selected(&selected)
+            .child(option().value("1").text("1"))
+            .children_signal(options.signal_vec().map(|i| option().text(i.to_string())));
+        let select = select.freeze();
+
+        render_now().await;
+        assert_eq!(
+            select.to_string(),
+            r#"<select><option value="1">1</option><option selected="">2</option></select>"#
+        );
+
+        options.lock_mut().push(3);
+        selected.set(3);
+        render_now().await;
+        assert_eq!(
+            select.to_string(),
+            r#"<select><option value="1">1</option><option>2</option><option selected="">3</option></select>"#
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn bind_checked_radio() {
+    create_app_container(APP_ID).await;
+
+    let first = Mutable::new(true);
+    let second = Mutable::new(false);
+    let radio = |id, checked: &Mutable<bool>| {
+        input()
+            .id(id)
+            .r#type("radio")
+            .name("group")
+            .bind_checked(checked)
+    };
+    mount(
+        APP_ID,
+        div()
+            .child(radio("first", &first))
+            .child(radio("second", &second)),
+    );
+    render_now().await;
+
+    let dom_radio = |id| -> web_sys::HtmlInputElement { query_element(id).unchecked_into() };
+    dom_radio("second").click();
+    assert!(!first.get());
+    assert!(second.get());
+
+    first.set(true);
+    render_now().await;
+    assert!(dom_radio("first").checked());
+    assert!(!dom_radio("second").checked());
+    assert!(!second.get());
+}
+
+#[wasm_bindgen_test]
+async fn bind_selected() {
+    create_app_container(APP_ID).await;
+
+    let selected = Mutable::new(2);
+    mount(
+        APP_ID,
+        select()
+            .id(INPUT_ID)
+            .bind_selected(&selected)
+            .children((1..=3).map(|i| option().value(i.to_string()).text(i.to_string()))),
+    );
+    render_now().await;
+
+    let dom_select: web_sys::HtmlSelectElement = query_element(INPUT_ID).unchecked_into();
+    assert_eq!(dom_select.value(), "2");
+
+    dom_select.set_value("3");
+    dispatch_input(&dom_select);
+    assert_eq!(selected.get(), 3);
+}
+
+#[wasm_bindgen_test]
+async fn hydrate_keeps_typed_value() {
+    create_app_container(APP_ID).await;
+    query_element(APP_ID).set_inner_html(r#"<input data-silkenweb="1" id="input" value="Server">"#);
+    dom_input().set_value("Typed before hydration");
+
+    let text = Mutable::new("Server".to_string());
+    let app = div()
+        .id(APP_ID)
+        .child(input().id(INPUT_ID).bind_value(&text));
+
+    render_now().await;
+    hydrate(APP_ID, app).await;
+    render_now().await;
+
+    assert_eq!(dom_input().value(), "Typed before hydration");
+    assert_eq!(text.get_cloned(), "Typed before hydration");
+}
+
+fn dom_input() -> web_sys::HtmlInputElement {
+    query_element(INPUT_ID).unchecked_into()
+}
+
+fn type_text(text: &str) {
+    let input = dom_input();
+    input.set_value(text);
+    dispatch_input(&input);
+}
+
+fn dispatch_input(target: &web_sys::EventTarget) {
+    target
+        .dispatch_event(&web_sys::Event::new("input").unwrap_throw())
+        .unwrap_throw();
+}
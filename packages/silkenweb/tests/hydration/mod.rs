@@ -3,10 +3,11 @@ use silkenweb::{
     dom::Hydro,
     elements::{
         html::{self, button, div, p},
+        mathml::{math, mi, mn},
         ElementEvents, HtmlElement,
     },
     hydration::hydrate,
-    node::element::{Const, GenericElement, ShadowRootParent},
+    node::element::{Const, GenericElement, ShadowRootOptions, ShadowRootParent},
     prelude::ParentElement,
     task::render_now,
     value::Sig,
@@ -279,6 +280,67 @@ async fn shadow_root_hydration() {
     );
 }
 
+#[wasm_bindgen_test]
+async fn adopted_stylesheet_hydration() {
+    app_container(APP_ID, "").await;
+    let shadow_root = query_element(APP_ID)
+        .attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+        .unwrap();
+    shadow_root.set_inner_html("<style>p { color: red }</style><p>Shadow child</p>");
+
+    let app = div()
+        .id(APP_ID)
+        .adopt_stylesheet("p { color: red }")
+        .attach_shadow_children([p().text("Shadow child")]);
+
+    render_now().await;
+    let stats = hydrate(APP_ID, app).await;
+    assert_eq!(stats.nodes_added(), 0);
+
+    let shadow_root = query_element(APP_ID).shadow_root().unwrap();
+    assert_eq!(r#"<p>Shadow child</p>"#, shadow_root.inner_html());
+    let adopted = js_sys::Reflect::get(&shadow_root, &"adoptedStyleSheets".into()).unwrap();
+    assert_eq!(js_sys::Array::from(&adopted).length(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn closed_shadow_root_hydration() {
+    app_container(APP_ID, "").await;
+
+    let app = div()
+        .id(APP_ID)
+        .shadow_root_options(ShadowRootOptions::default().closed(true))
+        .adopt_stylesheet("p { color: red }")
+        .attach_shadow_children([p().text("Shadow child")]);
+
+    render_now().await;
+    let stats = hydrate(APP_ID, app).await;
+    assert_eq!(stats.nodes_added(), 1);
+    assert!(query_element(APP_ID).shadow_root().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn mathml_namespace() {
+    app_container(APP_ID, r#"<math data-silkenweb="1"><mi>x</mi></math>"#).await;
+
+    let app = div()
+        .id(APP_ID)
+        .child(math().child(mi().text("x")).child(mn().text("2")));
+
+    test_hydrate(
+        APP_ID,
+        app,
+        r#"<div id="app"><math data-silkenweb="1"><mi>x</mi><mn>2</mn></math></div>"#,
+    )
+    .await;
+
+    let mn = query_element(APP_ID).query_selector("mn").unwrap().unwrap();
+    assert_eq!(
+        mn.namespace_uri().as_deref(),
+        Some("http://www.w3.org/1998/Math/MathML")
+    );
+}
+
 async fn app_container(id: &str, inner_html: &str) {
     create_app_container(id).await;
     query_element(id).set_inner_html(inner_html);
@@ -31,10 +31,15 @@ macro_rules! isomorphic_test {
 
 mod children;
 mod component;
+mod context;
 mod css;
+mod custom_element;
 mod element;
+mod form;
 mod hydration;
+mod lifecycle;
 mod template;
+mod view;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -0,0 +1,96 @@
+use std::{cell::RefCell, rc::Rc};
+
+use futures_signals::signal_vec::{MutableVec, SignalVecExt};
+use silkenweb::{
+    clone,
+    dom::Dry,
+    elements::html::{div, p, Div},
+    mount,
+    node::element::{Element, ParentElement},
+    task::render_now,
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use crate::{create_app_container, APP_ID};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+fn logged(log: &Log, name: &'static str) -> Div {
+    div()
+        .on_mount({
+            clone!(log);
+            move |_| log.borrow_mut().push(format!("mount {name}"))
+        })
+        .on_unmount({
+            clone!(log);
+            move |_| log.borrow_mut().push(format!("unmount {name}"))
+        })
+}
+
+#[wasm_bindgen_test]
+async fn mount_and_unmount() {
+    create_app_container(APP_ID).await;
+
+    let log = Log::default();
+    let mount_handle = mount(APP_ID, logged(&log, "parent").child(logged(&log, "child")));
+
+    assert_eq!(*log.borrow(), ["mount child", "mount parent"]);
+    log.borrow_mut().clear();
+
+    mount_handle.unmount();
+    assert_eq!(*log.borrow(), ["unmount parent", "unmount child"]);
+}
+
+#[wasm_bindgen_test]
+async fn signal_children() {
+    create_app_container(APP_ID).await;
+
+    let log = Log::default();
+    let children = MutableVec::new();
+    let _mount_handle = mount(
+        APP_ID,
+        div().children_signal(children.signal_vec_cloned().map({
+            clone!(log);
+            move |name| logged(&log, name)
+        })),
+    );
+
+    children.lock_mut().push_cloned("first");
+    children.lock_mut().push_cloned("second");
+    render_now().await;
+    assert_eq!(*log.borrow(), ["mount first", "mount second"]);
+    log.borrow_mut().clear();
+
+    // Moving a child doesn't unmount it.
+    children.lock_mut().move_from_to(0, 1);
+    render_now().await;
+    assert!(log.borrow().is_empty());
+
+    children.lock_mut().remove(0);
+    render_now().await;
+    assert_eq!(*log.borrow(), ["unmount second"]);
+    log.borrow_mut().clear();
+
+    children.lock_mut().clear();
+    render_now().await;
+    assert_eq!(*log.borrow(), ["unmount first"]);
+}
+
+isomorphic_test! {
+    async fn cleanup() {
+        let cleaned_up = Rc::new(RefCell::new(Vec::new()));
+        let element: Div<Dry> = div()
+            .on_cleanup({
+                clone!(cleaned_up);
+                move || cleaned_up.borrow_mut().push("parent")
+            })
+            .child(p().on_cleanup({
+                clone!(cleaned_up);
+                move || cleaned_up.borrow_mut().push("child")
+            }));
+
+        assert!(cleaned_up.borrow().is_empty());
+        drop(element);
+        assert_eq!(cleaned_up.borrow().len(), 2);
+    }
+}
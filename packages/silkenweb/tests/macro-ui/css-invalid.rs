@@ -0,0 +1,3 @@
+silkenweb::css!(content = ".a { color: red; } @unknown;", validate);
+
+fn main() {}
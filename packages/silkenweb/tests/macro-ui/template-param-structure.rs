@@ -0,0 +1,18 @@
+use silkenweb::{
+    dom::InstantiableDom,
+    elements::html::{div, Div},
+    template,
+};
+
+#[template]
+fn row<D: InstantiableDom>(active: &bool) -> Div<D> {
+    let _id = if *active { "a" } else { "b" };
+
+    if *active {
+        div()
+    } else {
+        div().id("a")
+    }
+}
+
+fn main() {}
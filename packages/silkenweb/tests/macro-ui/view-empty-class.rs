@@ -0,0 +1,5 @@
+use silkenweb::{dom::Dry, elements::html::Div, view};
+
+fn main() {
+    let _: Div<Dry> = view! { <div class=" " /> };
+}
@@ -3,7 +3,7 @@ use futures_signals::{
     signal_vec::{MutableVec, SignalVecExt},
 };
 use silkenweb::{
-    dom::Template,
+    dom::{InstantiableDom, Template},
     elements::{
         html::{div, Div},
         HtmlElement,
@@ -11,6 +11,7 @@ use silkenweb::{
     node::{element::Const, Node},
     prelude::ParentElement,
     task::render_now,
+    template,
     value::Sig,
 };
 use silkenweb_macros::cfg_browser;
@@ -89,6 +90,73 @@ isomorphic_test! {
     }
 }
 
+struct Item {
+    id: usize,
+    name: Mutable<String>,
+}
+
+#[template]
+fn item<D: InstantiableDom>(item: &Item) -> Div<D> {
+    div()
+        .class("item")
+        .id(format!("item-{}", item.id))
+        .child(div().text("Name: ").text(Sig(item.name.signal_cloned())))
+        .text(item.id.to_string())
+        .child(div().text("after"))
+}
+
+isomorphic_test! {
+    async fn template_macro() {
+        let first = Item { id: 1, name: Mutable::new("first".to_string()) };
+        let second = Item { id: 2, name: Mutable::new("second".to_string()) };
+        let first_node: Div = item(&first);
+        let second_node: Div = item(&second);
+        render_now().await;
+
+        let first_node: Node = first_node.into();
+        let second_node: Node = second_node.into();
+        assert_eq!(
+            first_node.to_string(),
+            r#"<div class="item" id="item-1"><div>Name: first</div>1<div>after</div></div>"#
+        );
+        assert_eq!(
+            second_node.to_string(),
+            r#"<div class="item" id="item-2"><div>Name: second</div>2<div>after</div></div>"#
+        );
+
+        first.name.set("renamed".to_string());
+        render_now().await;
+        assert_eq!(
+            first_node.to_string(),
+            r#"<div class="item" id="item-1"><div>Name: renamed</div>1<div>after</div></div>"#
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn template_macro_children_signal() {
+        let items = MutableVec::new_with_values(vec![0, 1]);
+        let parent: Node = div()
+            .children_signal(items.signal_vec().map(|id| {
+                item(&Item { id, name: Mutable::new(String::new()) })
+            }))
+            .into();
+        items.lock_mut().push(2);
+        render_now().await;
+
+        assert_eq!(
+            parent.to_string(),
+            concat!(
+                r#"<div>"#,
+                r#"<div class="item" id="item-0"><div>Name: </div>0<div>after</div></div>"#,
+                r#"<div class="item" id="item-1"><div>Name: </div>1<div>after</div></div>"#,
+                r#"<div class="item" id="item-2"><div>Name: </div>2<div>after</div></div>"#,
+                r#"</div>"#
+            )
+        );
+    }
+}
+
 #[cfg_browser(false)]
 mod dry {
     use silkenweb::{
@@ -0,0 +1,76 @@
+use futures_signals::signal::{Mutable, SignalExt};
+use silkenweb::{
+    dom::DefaultDom,
+    elements::{
+        html::{p, Div},
+        svg,
+    },
+    node::Node,
+    task::render_now,
+    value::Sig,
+    view,
+};
+
+isomorphic_test! {
+    async fn view_static() {
+        let app: Div = view! {
+            <div id="app" class="container">
+                <p>"Hello, " "world!"</p>
+                <input r#type="checkbox" checked aria-label="Check" />
+                <svg::svg width=10.0>
+                    <svg::rect />
+                </svg::svg>
+            </div>
+        };
+
+        assert_eq!(
+            Node::<DefaultDom>::from(app).to_string(),
+            concat!(
+                r#"<div id="app" class="container"><p>Hello, world!</p>"#,
+                r#"<input type="checkbox" checked="" aria-label="Check">"#,
+                r#"<svg width="10"><rect></rect></svg></div>"#
+            )
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn view_signals() {
+        let text = Mutable::new("first");
+        let child_text = Mutable::new("child");
+        let class = Mutable::new("a");
+        let app: Node = view! {
+            <div class={Sig(class.signal())}>
+                {Sig(text.signal())}
+                {Sig(child_text.signal().map(|text| p().text(text)))}
+            </div>
+        }
+        .into();
+
+        render_now().await;
+        assert_eq!(
+            app.to_string(),
+            r#"<div class="a">first<p>child</p></div>"#
+        );
+
+        text.set("second");
+        child_text.set("updated");
+        class.set("b");
+        render_now().await;
+        assert_eq!(
+            app.to_string(),
+            r#"<div class="b">second<p>updated</p></div>"#
+        );
+    }
+}
+
+isomorphic_test! {
+    async fn view_class_list() {
+        let app: Div = view! { <div class=" btn  primary "></div> };
+
+        assert_eq!(
+            Node::<DefaultDom>::from(app).to_string(),
+            r#"<div class="btn primary"></div>"#
+        );
+    }
+}
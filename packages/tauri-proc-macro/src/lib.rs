@@ -1,13 +1,23 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
 use proc_macro_error::{abort, abort_call_site, proc_macro_error};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
-    parse_macro_input,
+    parse_macro_input, parse_quote,
     token::{Pub, Semi},
-    FnArg, Pat, PatIdent, PatType, Signature, Visibility,
+    FnArg, GenericArgument, GenericParam, Ident, ItemFn, Pat, PatIdent, PatType, PathArguments,
+    ReturnType, Signature, Type, TypePath, Visibility,
 };
 
+/// The types of arguments that Tauri injects on the server. The client doesn't
+/// pass these.
+///
+/// They must be written as `tauri::State` etc., so user types with the same
+/// names aren't mistaken for them.
+const SERVER_ARGS: &[&str] = &["State", "Window", "AppHandle"];
+
 mod kw {
     use syn::custom_keyword;
 
@@ -24,6 +34,90 @@ pub fn client_command(attr: TokenStream, item: TokenStream) -> TokenStream {
         signature,
     } = parse_macro_input!(item);
 
+    client_stub(&visibility, &signature, fallible).into()
+}
+
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let Fallible(fallible) = parse_macro_input!(attr);
+    let server: ItemFn = parse_macro_input!(item);
+
+    expand_command(&server, fallible).into()
+}
+
+fn expand_command(server: &ItemFn, fallible: bool) -> proc_macro2::TokenStream {
+    let attrs = &server.attrs;
+    let client = client_stub(&server.vis, &server.sig, fallible);
+    let server = if fallible {
+        tag_errors(server)
+    } else {
+        server.to_token_stream()
+    };
+
+    quote!(
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        #[::tauri::command]
+        #server
+
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        #(#attrs)*
+        #client
+    )
+}
+
+/// Wrap a fallible server command so its errors are a
+/// `silkenweb_tauri::TaggedError`, which lets the client tell them apart from
+/// errors invoking the command.
+fn tag_errors(server: &ItemFn) -> proc_macro2::TokenStream {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = server;
+    let (ok_type, err_type) = result_types(&sig.output, true);
+    let mut outer_sig = sig.clone();
+    outer_sig.output = parse_quote!(
+        -> ::std::result::Result<#ok_type, ::silkenweb_tauri::TaggedError<#err_type>>
+    );
+
+    let mut arg_names = Vec::new();
+
+    for arg in &mut outer_sig.inputs {
+        match arg {
+            FnArg::Receiver(self_arg) => abort!(self_arg, "self arguments are not allowed"),
+            FnArg::Typed(PatType { pat, .. }) => match pat.as_mut() {
+                Pat::Ident(pat_ident) => {
+                    // The argument is moved into the inner function, so the outer one doesn't
+                    // need to be `mut`.
+                    pat_ident.mutability = None;
+                    arg_names.push(pat_ident.ident.clone());
+                }
+                _ => abort!(pat, "Arguments must be named"),
+            },
+        }
+    }
+
+    let name = &sig.ident;
+
+    quote!(
+        #(#attrs)*
+        #vis #outer_sig {
+            #sig #block
+
+            #name(#(#arg_names),*)
+                .await
+                .map_err(::silkenweb_tauri::TaggedError::Application)
+        }
+    )
+}
+
+fn client_stub(
+    visibility: &impl ToTokens,
+    signature: &Signature,
+    fallible: bool,
+) -> proc_macro2::TokenStream {
     if let Some(constness) = signature.constness {
         abort!(constness, "Function can't be const");
     }
@@ -32,36 +126,57 @@ pub fn client_command(attr: TokenStream, item: TokenStream) -> TokenStream {
         abort_call_site!("Function must be async");
     }
 
-    if let Some(variadic) = signature.variadic {
+    if let Some(variadic) = &signature.variadic {
         abort!(variadic, "Function can't be variadic");
     }
 
+    let mut signature = signature.clone();
+    signature.inputs = signature
+        .inputs
+        .into_iter()
+        .filter(|arg| !is_server_arg(arg))
+        .collect();
+    remove_unused_type_params(&mut signature);
+
     let fn_name = signature.ident.to_string();
-    let arg_names = signature.inputs.iter().map(|arg| match arg {
-        FnArg::Receiver(self_arg) => abort!(self_arg, "self arguments are not allowed"),
-        FnArg::Typed(PatType { pat, .. }) => match pat.as_ref() {
-            Pat::Ident(PatIdent { ident, .. }) => ident,
-            _ => abort!(pat, "Arguments must be named"),
-        },
-    });
+    let arg_names: Vec<&Ident> = signature
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Receiver(self_arg) => abort!(self_arg, "self arguments are not allowed"),
+            FnArg::Typed(PatType { pat, .. }) => match pat.as_ref() {
+                Pat::Ident(PatIdent { ident, .. }) => ident,
+                _ => abort!(pat, "Arguments must be named"),
+            },
+        })
+        .collect();
+    let arg_keys = arg_names.iter().map(|name| arg_key(name));
+
+    let (ok_type, err_type) = result_types(&signature.output, fallible);
+    signature.output = parse_quote!(
+        -> ::std::result::Result<#ok_type, ::silkenweb_tauri::CommandError<#err_type>>
+    );
 
     let result_handler = if fallible {
-        quote!(result
-            .map(|ok| serde_wasm_bindgen::from_value(ok).unwrap())
-            .map_err(|e| serde_wasm_bindgen::from_value(e).unwrap()))
+        quote!(match result {
+            Ok(ok) => serde_wasm_bindgen::from_value(ok).map_err(CommandError::Deserialize),
+            Err(e) => Err(::silkenweb_tauri::fallible_command_error(e)),
+        })
     } else {
-        quote!(serde_wasm_bindgen::from_value(result.unwrap()).unwrap())
+        quote!(
+            serde_wasm_bindgen::from_value(result.map_err(CommandError::Invoke)?)
+                .map_err(CommandError::Deserialize)
+        )
     };
 
     quote!(
         #visibility #signature {
-            use ::std::stringify;
-
             use ::silkenweb_tauri::{
                 js_sys::{self, Object, Reflect},
                 wasm_bindgen::{self, prelude::wasm_bindgen, JsValue},
                 wasm_bindgen_futures,
                 serde_wasm_bindgen,
+                CommandError,
             };
 
             #[wasm_bindgen(inline_js = r#"
@@ -77,7 +192,12 @@ pub fn client_command(attr: TokenStream, item: TokenStream) -> TokenStream {
 
             let args = Object::new();
 
-            #(Reflect::set(&args, &stringify!(#arg_names).into(), &serde_wasm_bindgen::to_value(&#arg_names).unwrap()).unwrap();)*
+            #(
+                let arg = serde_wasm_bindgen::to_value(&#arg_names)
+                    .map_err(|e| CommandError::Invoke(e.into()))?;
+                Reflect::set(&args, &#arg_keys.into(), &arg)
+                    .map_err(CommandError::Invoke)?;
+            )*
 
             let result = invoke(#fn_name.to_string(), args.into())
                 .await;
@@ -85,7 +205,121 @@ pub fn client_command(attr: TokenStream, item: TokenStream) -> TokenStream {
             #result_handler
         }
     )
-    .into()
+}
+
+/// The key for an argument in the object passed to `invoke`.
+///
+/// Tauri commands expect `lowerCamelCase` keys by default, so `user_name` is
+/// passed as `userName`.
+fn arg_key(name: &Ident) -> String {
+    let name = name.unraw().to_string();
+    let mut words = name.split('_').filter(|word| !word.is_empty());
+    let mut key = words.next().unwrap_or_default().to_string();
+
+    for word in words {
+        let mut chars = word.chars();
+
+        if let Some(first) = chars.next() {
+            key.extend(first.to_uppercase());
+            key.push_str(chars.as_str());
+        }
+    }
+
+    key
+}
+
+fn is_server_arg(arg: &FnArg) -> bool {
+    match arg {
+        FnArg::Typed(PatType { ty, .. }) => match ty.as_ref() {
+            Type::Path(TypePath { qself: None, path }) => {
+                let mut segments = path.segments.iter();
+
+                match (segments.next(), segments.next(), segments.next()) {
+                    (Some(krate), Some(ty), None) => {
+                        krate.ident == "tauri" && SERVER_ARGS.iter().any(|name| ty.ident == name)
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        },
+        FnArg::Receiver(_) => false,
+    }
+}
+
+/// Remove type parameters that were only used by server arguments, like the
+/// `R: tauri::Runtime` in `window: tauri::Window<R>`.
+fn remove_unused_type_params(signature: &mut Signature) {
+    let unused: Vec<Ident> = signature
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .filter(|ident| !mentions(&signature.inputs, ident) && !mentions(&signature.output, ident))
+        .collect();
+
+    let generics = &mut signature.generics;
+    generics.params = generics
+        .params
+        .clone()
+        .into_iter()
+        .filter(|param| !matches!(param, GenericParam::Type(ty) if unused.contains(&ty.ident)))
+        .collect();
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .clone()
+            .into_iter()
+            .filter(|predicate| !unused.iter().any(|ident| mentions(predicate, ident)))
+            .collect();
+    }
+}
+
+fn mentions(tokens: &impl ToTokens, ident: &Ident) -> bool {
+    fn tokens_mention(tokens: proc_macro2::TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(token) => &token == ident,
+            TokenTree::Group(group) => tokens_mention(group.stream(), ident),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        })
+    }
+
+    tokens_mention(tokens.to_token_stream(), ident)
+}
+
+/// The `Ok` and `Err` types for the client command.
+fn result_types(output: &ReturnType, fallible: bool) -> (Type, Type) {
+    if !fallible {
+        let ok_type = match output {
+            ReturnType::Default => parse_quote!(()),
+            ReturnType::Type(_, ty) => ty.as_ref().clone(),
+        };
+
+        return (ok_type, parse_quote!(::std::convert::Infallible));
+    }
+
+    if let ReturnType::Type(_, ty) = output {
+        if let Type::Path(TypePath { qself: None, path }) = ty.as_ref() {
+            let last = path.segments.last().unwrap();
+
+            if last.ident == "Result" {
+                if let PathArguments::AngleBracketed(args) = &last.arguments {
+                    let mut types = args.args.iter().filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty.clone()),
+                        _ => None,
+                    });
+
+                    if let (Some(ok_type), Some(err_type), None) =
+                        (types.next(), types.next(), types.next())
+                    {
+                        return (ok_type, err_type);
+                    }
+                }
+            }
+        }
+    }
+
+    abort!(output, "Fallible commands must return a `Result<T, E>`")
 }
 
 struct Fallible(bool);
@@ -130,3 +364,153 @@ impl Parse for ClientCommand {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, File, Item, ItemFn};
+
+    use super::{arg_key, expand_command, tag_errors};
+
+    #[test]
+    fn arg_keys() {
+        assert_eq!(arg_key(&parse_quote!(name)), "name");
+        assert_eq!(arg_key(&parse_quote!(user_name)), "userName");
+        assert_eq!(arg_key(&parse_quote!(r#type)), "type");
+        assert_eq!(arg_key(&parse_quote!(file_2_path)), "file2Path");
+    }
+
+    #[test]
+    fn command_split() {
+        let (server, client) = split(
+            parse_quote!(
+                /// Open a file
+                pub async fn open<R: tauri::Runtime>(
+                    window: tauri::Window<R>,
+                    state: State,
+                    path: String,
+                ) -> Result<(), String> {
+                    Ok(())
+                }
+            ),
+            true,
+        );
+
+        assert_eq!(
+            attrs(&server),
+            tokens(quote!(
+                #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+                #[::tauri::command]
+                #[doc = r" Open a file"]
+            ))
+        );
+        assert_eq!(tokens(&server.vis), "pub");
+        assert_eq!(
+            tokens(&server.sig),
+            tokens(quote!(
+                async fn open<R: tauri::Runtime>(
+                    window: tauri::Window<R>,
+                    state: State,
+                    path: String,
+                ) -> ::std::result::Result<(), ::silkenweb_tauri::TaggedError<String> >
+            ))
+        );
+
+        assert_eq!(
+            attrs(&client),
+            tokens(quote!(
+                #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+                #[doc = r" Open a file"]
+            ))
+        );
+        // `State` isn't `tauri::State`, so it's passed by the client.
+        assert_eq!(tokens(&client.vis), "pub");
+        assert_eq!(
+            tokens(&client.sig),
+            tokens(quote!(
+                async fn open(state: State, path: String)
+                    -> ::std::result::Result<(), ::silkenweb_tauri::CommandError<String> >
+            ))
+        );
+    }
+
+    #[test]
+    fn infallible_command_split() {
+        let (server, client) = split(
+            parse_quote!(
+                async fn greet(greeting: tauri::State<'_, Greeting>, name: String) -> String {
+                    format!("{}, {name}!", greeting.0)
+                }
+            ),
+            false,
+        );
+
+        assert_eq!(
+            tokens(&server.sig),
+            tokens(quote!(
+                async fn greet(greeting: tauri::State<'_, Greeting>, name: String) -> String
+            ))
+        );
+        assert_eq!(
+            tokens(&client.sig),
+            tokens(quote!(
+                async fn greet(name: String)
+                    -> ::std::result::Result<String, ::silkenweb_tauri::CommandError<::std::convert::Infallible> >
+            ))
+        );
+    }
+
+    #[test]
+    fn tagged_errors() {
+        let server: ItemFn = parse_quote!(
+            async fn open(mut path: String) -> Result<(), String> {
+                path.push('/');
+                Err(path)
+            }
+        );
+
+        let output = quote!(::std::result::Result<(), ::silkenweb_tauri::TaggedError<String>>);
+
+        assert_eq!(
+            tokens(tag_errors(&server)),
+            tokens(quote!(
+                async fn open(path: String) -> #output {
+                    async fn open(mut path: String) -> Result<(), String> {
+                        path.push('/');
+                        Err(path)
+                    }
+
+                    open(path)
+                        .await
+                        .map_err(::silkenweb_tauri::TaggedError::Application)
+                }
+            ))
+        );
+    }
+
+    /// Expand `command`, and return the server and client functions.
+    fn split(server: ItemFn, fallible: bool) -> (ItemFn, ItemFn) {
+        let expanded: File = syn::parse2(expand_command(&server, fallible)).unwrap();
+
+        match <[Item; 2]>::try_from(expanded.items) {
+            Ok([Item::Fn(server), Item::Fn(client)]) => (server, client),
+            _ => panic!("Expected a server and client function"),
+        }
+    }
+
+    fn attrs(item: &ItemFn) -> String {
+        let attrs = &item.attrs;
+        tokens(quote!(#(#attrs)*))
+    }
+
+    /// The tokens as a string, without whitespace, so `>>` and `> >` compare
+    /// equal.
+    fn tokens(tokens: impl ToTokens) -> String {
+        tokens
+            .into_token_stream()
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+}
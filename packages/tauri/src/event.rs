@@ -0,0 +1,161 @@
+//! Tauri events.
+//!
+//! Declare a typed event with [`Event::new`], then [`Event::listen`] for
+//! events from the backend, or [`Event::emit`] to send an event to the
+//! backend.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use futures::StreamExt;
+//! # use silkenweb_tauri::event::Event;
+//! const PROGRESS: Event<u32> = Event::new("progress");
+//!
+//! # async fn example() -> Result<(), wasm_bindgen::JsValue> {
+//! let mut progress = PROGRESS.listen().await?;
+//!
+//! while let Some(percent) = progress.next().await {
+//!     let percent = percent?;
+//!     // ...
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver},
+    Stream, StreamExt,
+};
+use futures_signals::signal::{self, Signal};
+use js_sys::{Function, Promise, Reflect};
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+/// A typed Tauri event.
+///
+/// The payload type `T` should match the payload type on the backend.
+pub struct Event<T> {
+    name: &'static str,
+    ty: PhantomData<fn() -> T>,
+}
+
+impl<T> Event<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            ty: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T: DeserializeOwned + 'static> Event<T> {
+    /// Listen for this event.
+    ///
+    /// The listener is unregistered when the [`EventStream`] is dropped.
+    pub async fn listen(&self) -> Result<EventStream<T>, JsValue> {
+        let (sender, receiver) = mpsc::unbounded();
+        let handler = Closure::new(move |event: JsValue| {
+            let payload = Reflect::get(&event, &"payload".into()).unwrap_or(JsValue::UNDEFINED);
+            // The receiver may have been dropped before we unregistered the handler.
+            let _ = sender.unbounded_send(serde_wasm_bindgen::from_value(payload));
+        });
+        let unlisten = listen(self.name, &handler).await?.unchecked_into();
+
+        Ok(EventStream {
+            receiver,
+            unlisten,
+            handler: Some(handler),
+        })
+    }
+}
+
+impl<T: Serialize> Event<T> {
+    /// Emit this event with `payload`.
+    pub async fn emit(&self, payload: &T) -> Result<(), JsValue> {
+        emit(self.name, serde_wasm_bindgen::to_value(payload)?).await?;
+        Ok(())
+    }
+}
+
+impl<T> Clone for Event<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Event<T> {}
+
+/// A [`Stream`] of event payloads.
+///
+/// Each item is the deserialized payload, or a deserialization error if the
+/// payload type doesn't match.
+pub struct EventStream<T> {
+    receiver: UnboundedReceiver<Result<T, serde_wasm_bindgen::Error>>,
+    unlisten: Function,
+    handler: Option<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl<T> EventStream<T> {
+    /// Convert into a [`Signal`] of the latest payload.
+    ///
+    /// The signal value is `None` until the first event is received.
+    pub fn signal(self) -> impl Signal<Item = Option<Result<T, serde_wasm_bindgen::Error>>> {
+        signal::from_stream(self)
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<T, serde_wasm_bindgen::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+impl<T> Drop for EventStream<T> {
+    fn drop(&mut self) {
+        let handler = self.handler.take();
+
+        match self.unlisten.call0(&JsValue::NULL) {
+            // Tauri can still call the handler until unregistering has finished, whether
+            // `unlisten` returns a `Promise` or `undefined`, so drop it asynchronously.
+            Ok(unlistening) => spawn_local(async move {
+                let _ = JsFuture::from(Promise::resolve(&unlistening)).await;
+                drop(handler);
+            }),
+            // The handler might still be registered, so it has to live forever.
+            Err(_) => {
+                if let Some(handler) = handler {
+                    handler.forget();
+                }
+            }
+        }
+    }
+}
+
+#[wasm_bindgen(inline_js = r#"
+    export async function listen(name, handler) {
+        return await window.__TAURI__.event.listen(name, handler);
+    }
+
+    export async function emit(name, payload) {
+        return await window.__TAURI__.event.emit(name, payload);
+    }
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    async fn listen(name: &str, handler: &Closure<dyn FnMut(JsValue)>) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    async fn emit(name: &str, payload: JsValue) -> Result<JsValue, JsValue>;
+}
@@ -1,11 +1,23 @@
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::{self, Debug, Display},
+};
+
 pub use js_sys;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 pub use serde_wasm_bindgen;
+use wasm_bindgen::JsValue;
+
+pub mod event;
+
 /// Add a Tauri command signature to the client.
 ///
 /// See the tauri docs for an explanation of [commands](https://tauri.studio/docs/guides/command/).
 ///
 /// ⚠️ **It's the clients responsibility to ensure client and server command
-/// signatures match.** ⚠️
+/// signatures match.** ⚠️ Use [`command`] to generate the client and server
+/// from a single definition.
 ///
 /// Commands can fail by returning a [`Result`], or be infallible by returning
 /// either a plain value or `()`. To specify an infallible command, use
@@ -13,9 +25,23 @@ pub use serde_wasm_bindgen;
 /// command that returns a [`Result`], use
 /// `#[silkenweb_tauri::client_command(fallible)]`.
 ///
+/// The generated client command returns a [`CommandError`] if anything goes
+/// wrong, rather than panicking. A fallible command declared as returning
+/// `Result<T, E>` will return `Result<T, CommandError<E>>`. An infallible
+/// command declared as returning `T` will return `Result<T, CommandError>`.
+///
+/// The server side of a fallible command should return its errors as a
+/// [`TaggedError`], so the client can tell them apart from errors invoking
+/// the command. [`command`] does this for you. Untagged errors are still
+/// application errors if they deserialize as `E`, but then an error invoking
+/// the command that also deserializes as `E`, for example when `E` is
+/// `String`, is reported as an application error too.
+///
 /// Commands can specify a visibility with `pub` or `pub(crate)` etc. Commands
 /// must be `async`. All argument types must be `serde::Serialize`, and all
-/// return types must be `serde::Deserialize`.
+/// return types must be `serde::Deserialize`. Arguments are passed with
+/// `lowerCamelCase` names, like `userName` for `user_name`, which is what
+/// `#[tauri::command]` expects by default.
 ///
 /// # Examples
 ///
@@ -57,6 +83,128 @@ pub use serde_wasm_bindgen;
 ///
 /// [`Result`]: std::result::Result
 pub use silkenweb_tauri_proc_macro::client_command;
+/// Define a Tauri command for both the server and the client.
+///
+/// This takes a complete command definition, and generates:
+///
+/// - a `#[tauri::command]` for the server, when not compiling for
+///   `wasm32-unknown-unknown`.
+/// - a [`client_command`] stub for the client, when compiling for
+///   `wasm32-unknown-unknown`.
+///
+/// Put the command definitions in a crate that's shared between the client and
+/// server, so the client and server signatures can't get out of sync. The
+/// shared crate should depend on `tauri` for non `wasm32` targets. The
+/// `fallible` and `infallible` arguments are the same as for
+/// [`client_command`].
+///
+/// Arguments that Tauri injects on the server are removed from the client's
+/// signature, along with any type parameters that only they use. Their types
+/// must be written as `tauri::State`, `tauri::Window` or `tauri::AppHandle`,
+/// so they're not confused with your own types.
+///
+/// # Example
+///
+/// This isn't compiled as a doc test, as the server side needs `tauri`.
+///
+/// ```ignore
+/// #[silkenweb_tauri::command(infallible)]
+/// pub async fn greet(greeting: tauri::State<'_, Greeting>, name: String) -> String {
+///     format!("{}, {name}!", greeting.0)
+/// }
+/// ```
+///
+/// On the server, add it to the handler with
+/// `tauri::generate_handler![shared::greet]`. On the client, call it with
+/// `shared::greet(name).await`, which returns a `Result<String, CommandError>`.
+///
+/// On the server, a fallible command's errors are wrapped in a
+/// [`TaggedError`].
+pub use silkenweb_tauri_proc_macro::command;
 pub use static_assertions;
 pub use wasm_bindgen;
 pub use wasm_bindgen_futures;
+
+/// An error from a Tauri command.
+///
+/// `E` is the application error type for fallible commands. For infallible
+/// commands it's [`Infallible`].
+#[derive(Debug)]
+pub enum CommandError<E = Infallible> {
+    /// The command couldn't be invoked, or the arguments couldn't be
+    /// serialized.
+    ///
+    /// For fallible commands, this is used for any error that isn't a
+    /// [`TaggedError`] and doesn't deserialize as `E`.
+    Invoke(JsValue),
+    /// The result, or a [`TaggedError`]'s application error, couldn't be
+    /// deserialized.
+    Deserialize(serde_wasm_bindgen::Error),
+    /// The command returned an error.
+    Application(E),
+}
+
+impl<E: Display> Display for CommandError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Invoke(e) => match e.as_string() {
+                Some(e) => write!(f, "Failed to invoke command: {e}"),
+                None => write!(f, "Failed to invoke command: {e:?}"),
+            },
+            CommandError::Deserialize(e) => write!(f, "Failed to deserialize result: {e}"),
+            CommandError::Application(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: Debug + Display> Error for CommandError<E> {}
+
+/// Convert an error from invoking a fallible command.
+///
+/// This is used by the generated client commands.
+#[doc(hidden)]
+pub fn fallible_command_error<E: DeserializeOwned>(e: JsValue) -> CommandError<E> {
+    if let Some(error) = tagged_application_error(&e) {
+        return match serde_wasm_bindgen::from_value(error) {
+            Ok(error) => CommandError::Application(error),
+            Err(e) => CommandError::Deserialize(e),
+        };
+    }
+
+    match serde_wasm_bindgen::from_value(e.clone()) {
+        Ok(error) => CommandError::Application(error),
+        Err(_) => CommandError::Invoke(e),
+    }
+}
+
+/// The application error, if `e` is a serialized [`TaggedError`].
+fn tagged_application_error(e: &JsValue) -> Option<JsValue> {
+    if !e.is_object() {
+        return None;
+    }
+
+    let kind = js_sys::Reflect::get(e, &"kind".into()).ok()?;
+
+    if kind.as_string()? != "application" {
+        return None;
+    }
+
+    js_sys::Reflect::get(e, &"error".into()).ok()
+}
+
+/// An application error from the server side of a fallible command.
+///
+/// This serializes as `{"kind": "application", "error": ...}`, so the client
+/// can tell application errors apart from errors invoking the command, even
+/// if they have the same type.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "error", rename_all = "camelCase")]
+pub enum TaggedError<E> {
+    Application(E),
+}
+
+impl<E> From<E> for TaggedError<E> {
+    fn from(e: E) -> Self {
+        Self::Application(e)
+    }
+}
@@ -0,0 +1,158 @@
+use serde::Deserialize;
+use silkenweb_tauri::{client_command, CommandError};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// `tauri` isn't a dependency, so these only compile if the arguments that
+// Tauri injects on the server are removed from the client.
+
+#[client_command(infallible)]
+async fn greet(greeting: tauri::State<'_, Greeting>, name: String) -> String;
+
+#[client_command(fallible)]
+async fn open<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    window: tauri::Window<R>,
+    path: String,
+) -> Result<(), String>;
+
+#[client_command(infallible)]
+async fn count<R>(window: tauri::Window<R>, step: u32) -> u32
+where
+    R: tauri::Runtime;
+
+#[client_command(infallible)]
+async fn full_name(first_name: String, last_name: String) -> String;
+
+#[client_command(fallible)]
+async fn divide(numerator: u32, denominator: u32) -> Result<u32, DivideError>;
+
+#[client_command(fallible)]
+async fn unregistered() -> Result<(), DivideError>;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+enum DivideError {
+    DivideByZero,
+}
+
+#[test]
+fn server_args_removed() {
+    // The futures don't invoke anything unless they're polled.
+    let _greet = async {
+        let _: Result<String, CommandError> = greet("Ada".to_string()).await;
+    };
+    let _open = async {
+        let _: Result<(), CommandError<String>> = open("/".to_string()).await;
+    };
+    let _count = async {
+        let _: Result<u32, CommandError> = count(1).await;
+    };
+}
+
+#[wasm_bindgen_test]
+async fn success() {
+    mock_invoke();
+    assert_eq!(greet("Ada".to_string()).await.unwrap(), "Hello, Ada!");
+}
+
+#[wasm_bindgen_test]
+async fn camel_case_args() {
+    mock_invoke();
+    assert_eq!(
+        full_name("Ada".to_string(), "Lovelace".to_string())
+            .await
+            .unwrap(),
+        "Ada Lovelace"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn application_error() {
+    mock_invoke();
+
+    match open("missing".to_string()).await {
+        Err(CommandError::Application(e)) => assert_eq!(e, "Not found"),
+        result => panic!("Expected an application error, got {result:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn untagged_error() {
+    mock_invoke();
+    assert_eq!(divide(6, 3).await.unwrap(), 2);
+
+    match divide(1, 0).await {
+        Err(CommandError::Application(e)) => assert_eq!(e, DivideError::DivideByZero),
+        result => panic!("Expected an application error, got {result:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn tagged_deserialize_error() {
+    mock_invoke();
+
+    // The error is tagged as an application error, but it isn't a `DivideError`.
+    assert!(matches!(
+        divide(7, 2).await,
+        Err(CommandError::Deserialize(_))
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn fallible_invoke_error() {
+    mock_invoke();
+
+    match unregistered().await {
+        Err(CommandError::Invoke(e)) => {
+            assert_eq!(e.as_string().unwrap(), "Unknown command: unregistered")
+        }
+        result => panic!("Expected an invoke error, got {result:?}"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn infallible_invoke_error() {
+    mock_invoke();
+    assert!(matches!(count(0).await, Err(CommandError::Invoke(_))));
+}
+
+#[wasm_bindgen(inline_js = r#"
+    export function mock_invoke() {
+        window.__TAURI__ = window.__TAURI__ || {};
+        window.__TAURI__.invoke = async (name, args) => {
+            switch (name) {
+                case "greet":
+                    return `Hello, ${args.name}!`;
+                case "open":
+                    if (args.path === "missing") {
+                        throw { kind: "application", error: "Not found" };
+                    }
+
+                    return null;
+                case "full_name":
+                    return `${args.firstName} ${args.lastName}`;
+                case "count":
+                    if (args.step === 0) {
+                        throw "Step must be positive";
+                    }
+
+                    return args.step;
+                case "divide":
+                    if (args.denominator === 0) {
+                        throw "DivideByZero";
+                    }
+
+                    if (args.numerator % args.denominator !== 0) {
+                        throw { kind: "application", error: "Not a whole number" };
+                    }
+
+                    return args.numerator / args.denominator;
+                default:
+                    throw `Unknown command: ${name}`;
+            }
+        };
+    }
+"#)]
+extern "C" {
+    fn mock_invoke();
+}
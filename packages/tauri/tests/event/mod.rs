@@ -0,0 +1,115 @@
+use futures::StreamExt;
+use silkenweb_tauri::event::Event;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const COUNT: Event<u32> = Event::new("count");
+const COUNT_TEXT: Event<String> = Event::new("count");
+
+#[wasm_bindgen_test]
+async fn listen() {
+    mock_tauri();
+    let mut count = COUNT.listen().await.unwrap();
+
+    COUNT.emit(&1).await.unwrap();
+    COUNT.emit(&2).await.unwrap();
+
+    assert_eq!(count.next().await.unwrap().unwrap(), 1);
+    assert_eq!(count.next().await.unwrap().unwrap(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn payload_type_mismatch() {
+    mock_tauri();
+    let mut count = COUNT.listen().await.unwrap();
+
+    COUNT_TEXT.emit(&"one".to_string()).await.unwrap();
+
+    assert!(count.next().await.unwrap().is_err());
+}
+
+#[wasm_bindgen_test]
+async fn unlisten_on_drop() {
+    mock_tauri();
+    let count = COUNT.listen().await.unwrap();
+    assert_eq!(listener_count("count"), 1);
+
+    drop(count);
+    JsFuture::from(unlistened()).await.unwrap();
+
+    assert_eq!(listener_count("count"), 0);
+    assert_eq!(
+        handler_errors(),
+        0,
+        "The handler is kept alive until unlistening finishes"
+    );
+}
+
+// A mock of the Tauri event API. Like Tauri v1, `unlisten` returns
+// `undefined`, but unregistering is asynchronous, so the handler can still be
+// called until it has finished.
+#[wasm_bindgen(inline_js = r#"
+    let listeners;
+    let handlerErrors;
+    let unlistening;
+
+    export function mock_tauri() {
+        listeners = new Map();
+        handlerErrors = 0;
+        unlistening = Promise.resolve();
+
+        window.__TAURI__ = {
+            event: {
+                async listen(name, handler) {
+                    const handlers = listeners.get(name) || new Set();
+                    handlers.add(handler);
+                    listeners.set(name, handlers);
+
+                    return () => {
+                        unlistening = new Promise(resolve => queueMicrotask(() => {
+                            call(handler, { payload: 0 });
+                            handlers.delete(handler);
+                            resolve();
+                        }));
+                    };
+                },
+
+                async emit(name, payload) {
+                    for (const handler of listeners.get(name) || []) {
+                        call(handler, { payload });
+                    }
+                }
+            }
+        };
+    }
+
+    function call(handler, event) {
+        try {
+            handler(event);
+        } catch {
+            handlerErrors += 1;
+        }
+    }
+
+    export function listener_count(name) {
+        return (listeners.get(name) || new Set()).size;
+    }
+
+    export function handler_errors() {
+        return handlerErrors;
+    }
+
+    export function unlistened() {
+        return unlistening;
+    }
+"#)]
+extern "C" {
+    fn mock_tauri();
+
+    fn listener_count(name: &str) -> u32;
+
+    fn handler_errors() -> u32;
+
+    fn unlistened() -> js_sys::Promise;
+}
@@ -0,0 +1,6 @@
+use wasm_bindgen_test::wasm_bindgen_test_configure;
+
+mod command;
+mod event;
+
+wasm_bindgen_test_configure!(run_in_browser);